@@ -0,0 +1,73 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use advent_of_code_2024::{YEAR, day2::explain_reports};
+use aoc_tools::config::Config;
+
+const USAGE: &str =
+    "usage: day2_explain [--json] [--unsafe-only] [--max-removals <n>] [input path]";
+
+fn main() {
+    let mut is_json = false;
+    let mut is_unsafe_only = false;
+    let mut max_removals: usize = 1;
+    let mut input_path: Option<PathBuf> = None;
+
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => is_json = true,
+            "--unsafe-only" => is_unsafe_only = true,
+            "--max-removals" => {
+                max_removals = args
+                    .next()
+                    .and_then(|n| n.parse::<usize>().ok())
+                    .unwrap_or_else(|| {
+                        eprintln!("{USAGE}");
+                        process::exit(2);
+                    });
+            }
+            _ if arg.starts_with("--") => {
+                eprintln!("{USAGE}");
+                process::exit(2);
+            }
+            _ => input_path = Some(PathBuf::from(arg)),
+        }
+    }
+
+    // where the runner reads this day's input from, unless another input is given
+    let input_path = input_path.unwrap_or_else(|| {
+        Config::load(Path::new(""))
+            .map(|config| config.input_path(YEAR, 2))
+            .unwrap_or_else(|e| {
+                eprintln!("{e}");
+                process::exit(1);
+            })
+    });
+
+    let input = fs::read_to_string(&input_path).unwrap_or_else(|e| {
+        eprintln!("failed to read {}: {e}", input_path.display());
+        process::exit(1);
+    });
+
+    let explanations = explain_reports(&input, max_removals).unwrap_or_else(|e| {
+        eprintln!("{}: {e}", input_path.display());
+        process::exit(1);
+    });
+
+    for (line, explanation) in explanations.iter().enumerate() {
+        if is_unsafe_only && explanation.is_safe() {
+            continue;
+        }
+
+        if is_json {
+            println!("{}", explanation.to_json_line());
+        } else {
+            println!("line {}: {}", line + 1, explanation);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use advent_of_code_2024::{YEAR, day4::find_words};
+use aoc_tools::config::Config;
+use aoc_tools::visualize::{self, GridFrame, ImageStyle, Rgb};
+
+const USAGE: &str = "usage: day4_visualize [--word <word>]... [--png <path>] [input path]";
+
+// one colour per word, so overlapping words stay distinguishable
+const WORD_COLORS: [Rgb; 4] = [Rgb::YELLOW, Rgb::CYAN, Rgb::MAGENTA, Rgb::GREEN];
+
+fn main() {
+    let mut words: Vec<String> = Vec::new();
+    let mut png_path: Option<PathBuf> = None;
+    let mut input_path: Option<PathBuf> = None;
+
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--word" | "--png" => {
+                let value = args.next().unwrap_or_else(|| {
+                    eprintln!("{USAGE}");
+                    process::exit(2);
+                });
+
+                if arg == "--word" {
+                    words.push(value);
+                } else {
+                    png_path = Some(PathBuf::from(value));
+                }
+            }
+            _ if arg.starts_with("--") => {
+                eprintln!("{USAGE}");
+                process::exit(2);
+            }
+            _ => input_path = Some(PathBuf::from(arg)),
+        }
+    }
+
+    if words.is_empty() {
+        words.push(String::from("XMAS"));
+    }
+
+    // where the runner reads this day's input from, unless another input is given
+    let input_path = input_path.unwrap_or_else(|| {
+        Config::load(Path::new(""))
+            .map(|config| config.input_path(YEAR, 4))
+            .unwrap_or_else(|e| {
+                eprintln!("{e}");
+                process::exit(1);
+            })
+    });
+
+    let input = fs::read_to_string(&input_path).unwrap_or_else(|e| {
+        eprintln!("failed to read {}: {e}", input_path.display());
+        process::exit(1);
+    });
+
+    let words: Vec<&str> = words.iter().map(String::as_str).collect();
+    let word_matches = find_words(&input, &words);
+
+    let mut frame = GridFrame::parse(&input);
+
+    for word_match in &word_matches {
+        let word_i = words
+            .iter()
+            .position(|word| *word == word_match.word())
+            .unwrap();
+
+        for (row_i, col_i) in word_match.cells() {
+            frame.highlight(row_i, col_i, WORD_COLORS[word_i % WORD_COLORS.len()]);
+        }
+    }
+
+    match png_path {
+        Some(png_path) => {
+            if let Err(e) = visualize::write_png(&frame, &ImageStyle::default(), &png_path) {
+                eprintln!("failed to write {}: {e}", png_path.display());
+                process::exit(1);
+            }
+        }
+        None => print!("{}", frame.to_ansi()),
+    }
+
+    println!("{} matches of {}", word_matches.len(), words.join(", "));
+}
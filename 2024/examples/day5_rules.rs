@@ -0,0 +1,103 @@
+use std::{
+    env, fmt, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use advent_of_code_2024::{
+    YEAR,
+    day5::{PageNumber, analyze_updates, find_rule_paths},
+};
+use aoc_tools::config::Config;
+
+const USAGE: &str = "usage: day5_rules [--invalid-only] [--before <page> <page>] [input path]";
+
+fn parse_page_number(arg: Option<String>) -> PageNumber {
+    arg.and_then(|p| p.parse::<PageNumber>().ok())
+        .unwrap_or_else(|| {
+            eprintln!("{USAGE}");
+            process::exit(2);
+        })
+}
+
+fn exit_on_error<T>(input_path: &Path, e: impl fmt::Display) -> T {
+    eprintln!("{}: {e}", input_path.display());
+    process::exit(1);
+}
+
+fn main() {
+    let mut is_invalid_only = false;
+    let mut before_query: Option<(PageNumber, PageNumber)> = None;
+    let mut input_path: Option<PathBuf> = None;
+
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--invalid-only" => is_invalid_only = true,
+            "--before" => {
+                let page_number_before = parse_page_number(args.next());
+                let page_number = parse_page_number(args.next());
+                before_query = Some((page_number_before, page_number));
+            }
+            _ if arg.starts_with("--") => {
+                eprintln!("{USAGE}");
+                process::exit(2);
+            }
+            _ => input_path = Some(PathBuf::from(arg)),
+        }
+    }
+
+    // where the runner reads this day's input from, unless another input is given
+    let input_path = input_path.unwrap_or_else(|| {
+        Config::load(Path::new(""))
+            .map(|config| config.input_path(YEAR, 5))
+            .unwrap_or_else(|e| {
+                eprintln!("{e}");
+                process::exit(1);
+            })
+    });
+
+    let input = fs::read_to_string(&input_path).unwrap_or_else(|e| {
+        eprintln!("failed to read {}: {e}", input_path.display());
+        process::exit(1);
+    });
+
+    if let Some((page_number_before, page_number)) = before_query {
+        let rule_paths = find_rule_paths(&input, page_number_before, page_number)
+            .unwrap_or_else(|e| exit_on_error(&input_path, e));
+
+        if rule_paths.is_empty() {
+            println!("no update has both {page_number_before} and {page_number}");
+        }
+
+        for (update_i, rule_path) in rule_paths {
+            match rule_path {
+                Some(path) => {
+                    let path: Vec<String> = path.iter().map(|p| p.to_string()).collect();
+                    println!(
+                        "update {}: {page_number_before} must come before {page_number}: {}",
+                        update_i + 1,
+                        path.join(" -> ")
+                    );
+                }
+                None => println!(
+                    "update {}: no rules require {page_number_before} to come before {page_number}",
+                    update_i + 1
+                ),
+            }
+        }
+
+        return;
+    }
+
+    let analyses = analyze_updates(&input).unwrap_or_else(|e| exit_on_error(&input_path, e));
+
+    for (i, analysis) in analyses.iter().enumerate() {
+        if is_invalid_only && analysis.is_valid() {
+            continue;
+        }
+
+        println!("update {}: {}", i + 1, analysis);
+    }
+}
@@ -0,0 +1,121 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+    time::Duration,
+};
+
+use advent_of_code_2024::{YEAR, day6::guard_walk};
+use aoc_tools::config::Config;
+use aoc_tools::visualize::{Animation, GridFrame, ImageStyle, Rgb};
+
+const USAGE: &str = "usage: day6_visualize [--gif <path>] [--frames <dir>] [--every <n>] [--delay <ms>] \
+    [--cell-size <px>] [input path]";
+
+fn parse_arg<T: std::str::FromStr>(arg: Option<String>) -> T {
+    arg.and_then(|arg| arg.parse::<T>().ok())
+        .unwrap_or_else(|| {
+            eprintln!("{USAGE}");
+            process::exit(2);
+        })
+}
+
+fn main() {
+    let mut gif_path: Option<PathBuf> = None;
+    let mut frames_dir: Option<PathBuf> = None;
+    let mut every: usize = 1;
+    let mut delay = Duration::from_millis(50);
+    let mut style = ImageStyle::default();
+    let mut input_path: Option<PathBuf> = None;
+
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--gif" => gif_path = Some(parse_arg(args.next())),
+            "--frames" => frames_dir = Some(parse_arg(args.next())),
+            "--every" => every = parse_arg::<usize>(args.next()).max(1),
+            "--delay" => delay = Duration::from_millis(parse_arg(args.next())),
+            "--cell-size" => style.cell_size = parse_arg::<usize>(args.next()).max(1),
+            _ if arg.starts_with("--") => {
+                eprintln!("{USAGE}");
+                process::exit(2);
+            }
+            _ => input_path = Some(PathBuf::from(arg)),
+        }
+    }
+
+    // where the runner reads this day's input from, unless another input is given
+    let input_path = input_path.unwrap_or_else(|| {
+        Config::load(Path::new(""))
+            .map(|config| config.input_path(YEAR, 6))
+            .unwrap_or_else(|e| {
+                eprintln!("{e}");
+                process::exit(1);
+            })
+    });
+
+    let input = fs::read_to_string(&input_path).unwrap_or_else(|e| {
+        eprintln!("failed to read {}: {e}", input_path.display());
+        process::exit(1);
+    });
+
+    let walk = guard_walk(&input);
+
+    let mut frame = GridFrame::parse(&input);
+    let mut animation = Animation::new();
+    let mut previous_guard: Option<(usize, usize)> = None;
+
+    for (step_i, step) in walk.steps().iter().enumerate() {
+        // the guard leaves a trail of visited cells behind
+        if let Some((row_i, col_i)) = previous_guard {
+            frame.set_cell(row_i, col_i, 'X');
+            frame.highlight(row_i, col_i, Rgb::BLUE);
+        }
+
+        let (row_i, col_i) = step.row_col();
+        frame.set_cell(row_i, col_i, step.guard_char());
+        frame.highlight(row_i, col_i, Rgb::RED);
+        previous_guard = Some((row_i, col_i));
+
+        if step_i % every == 0 || step_i + 1 == walk.steps().len() {
+            animation.push(frame.clone());
+        }
+    }
+
+    let mut is_written = false;
+
+    if let Some(gif_path) = gif_path {
+        if let Err(e) = animation.write_gif(&style, delay, &gif_path) {
+            eprintln!("failed to write {}: {e}", gif_path.display());
+            process::exit(1);
+        }
+
+        is_written = true;
+    }
+
+    if let Some(frames_dir) = frames_dir {
+        if let Err(e) = animation.write_png_frames(&style, &frames_dir) {
+            eprintln!("failed to write frames to {}: {e}", frames_dir.display());
+            process::exit(1);
+        }
+
+        is_written = true;
+    }
+
+    if !is_written && let Err(e) = animation.play(delay) {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+
+    println!(
+        "{} steps, {} cells visited{}",
+        walk.steps().len(),
+        walk.num_visited(),
+        if walk.is_loop() {
+            ", stuck in a loop"
+        } else {
+            ""
+        }
+    );
+}
@@ -0,0 +1,13 @@
+#![no_main]
+
+use std::sync::LazyLock;
+
+use aoc_tools::{aoc_solutions, fuzz, runner::Solution};
+use libfuzzer_sys::fuzz_target;
+
+extern crate advent_of_code_2024;
+
+static SOLUTIONS: LazyLock<Vec<Solution>> = LazyLock::new(|| aoc_solutions! { lib = advent_of_code_2024 });
+
+// every generator and part sees every input; malformed inputs must come back as errors rather than panics
+fuzz_target!(|data: &[u8]| fuzz::run_solutions(&SOLUTIONS, data));
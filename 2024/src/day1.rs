@@ -1,65 +1,64 @@
 use std::collections::HashMap;
 
 use aoc_runner_derive::aoc;
+use aoc_tools::{
+    answer::{Answer, SolutionError, checked_add, checked_mul},
+    input::{InputError, parse_number},
+};
 
-fn parse_option_value(option: &Option<&str>) -> u32 {
-    option
-        .expect("expected value to not be empty!")
-        .parse::<u32>()
-        .expect("failed to parse value to u32!")
-}
-
-fn parse_input_day1(input: &str) -> (Vec<u32>, Vec<u32>) {
+fn parse_input_day1(input: &str) -> Result<(Vec<u32>, Vec<u32>), InputError> {
     let mut left_col = Vec::<u32>::new();
     let mut right_col = Vec::<u32>::new();
 
-    input
-        .lines()
-        .map(|l| l.split_whitespace())
-        .for_each(|mut split_line| {
-            let left = parse_option_value(&split_line.next());
-            let right = parse_option_value(&split_line.next());
+    for (line_i, line) in input.lines().enumerate() {
+        let mut split_line = line.split_whitespace();
+
+        let (Some(left), Some(right), None) =
+            (split_line.next(), split_line.next(), split_line.next())
+        else {
+            return Err(InputError::at_line(line_i, "expected two numbers"));
+        };
 
-            left_col.push(left);
-            right_col.push(right);
-        });
+        left_col.push(parse_number(left, line_i)?);
+        right_col.push(parse_number(right, line_i)?);
+    }
 
-    (left_col, right_col)
+    Ok((left_col, right_col))
 }
 
 #[aoc(day1, part1)]
-fn part1(input: &str) -> u32 {
-    let mut lists = parse_input_day1(input);
+fn part1(input: &str) -> Result<Answer, SolutionError> {
+    let mut lists = parse_input_day1(input)?;
 
     process_part1(&mut lists)
 }
 
-fn process_part1(lists: &mut (Vec<u32>, Vec<u32>)) -> u32 {
+fn process_part1(lists: &mut (Vec<u32>, Vec<u32>)) -> Result<Answer, SolutionError> {
     let (left_list, right_list) = lists;
 
     left_list.sort();
     right_list.sort();
 
-    let mut sum = 0;
+    let mut sum: u64 = 0;
 
     for i in 0..left_list.len() {
         let left = *left_list.get(i).unwrap();
         let right = *right_list.get(i).unwrap();
 
-        sum += left.abs_diff(right);
+        sum = checked_add(sum, left.abs_diff(right).into())?;
     }
 
-    sum
+    Ok(Answer::from(sum))
 }
 
 #[aoc(day1, part2)]
-fn part2(input: &str) -> u32 {
-    let mut lists = parse_input_day1(input);
+fn part2(input: &str) -> Result<Answer, SolutionError> {
+    let mut lists = parse_input_day1(input)?;
 
     process_part2(&mut lists)
 }
 
-fn process_part2(lists: &mut (Vec<u32>, Vec<u32>)) -> u32 {
+fn process_part2(lists: &mut (Vec<u32>, Vec<u32>)) -> Result<Answer, SolutionError> {
     let (left_list, right_list) = lists;
 
     let mut right_list_count_map = HashMap::<u32, u32>::new();
@@ -75,13 +74,15 @@ fn process_part2(lists: &mut (Vec<u32>, Vec<u32>)) -> u32 {
         };
     }
 
-    let mut sum: u32 = 0;
+    let mut sum: u64 = 0;
 
     for num in left_list {
-        sum += *num * right_list_count_map.get(num).unwrap_or(&(0));
+        let count = *right_list_count_map.get(num).unwrap_or(&(0));
+
+        sum = checked_add(sum, checked_mul(u64::from(*num), u64::from(count))?)?;
     }
 
-    sum
+    Ok(Answer::from(sum))
 }
 
 #[cfg(test)]
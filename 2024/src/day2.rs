@@ -1,20 +1,21 @@
+use std::{cmp::Ordering, error::Error, fmt};
+
 use aoc_runner_derive::aoc;
+use aoc_tools::input::{InputError, parse_number};
+use rand::{Rng, SeedableRng, rngs::StdRng};
 
 struct ReportPart1 {
     values: Vec<u16>,
 }
 
 impl ReportPart1 {
-    fn from(input: &str) -> Vec<ReportPart1> {
-        input
-            .lines()
-            .map(|l| ReportPart1 {
-                values: l
-                    .split_whitespace()
-                    .map(|n| n.parse::<u16>().unwrap())
-                    .collect(),
-            })
-            .collect()
+    fn from(input: &str) -> Result<Vec<ReportPart1>, InputError> {
+        parse_reports(input).map(|reports| {
+            reports
+                .into_iter()
+                .map(|values| ReportPart1 { values })
+                .collect()
+        })
     }
 
     fn is_ascending(&self) -> bool {
@@ -76,143 +77,632 @@ impl ReportPart1 {
 }
 
 #[aoc(day2, part1)]
-fn part1(input: &str) -> u32 {
-    let reports = ReportPart1::from(input);
+fn part1(input: &str) -> Result<u32, InputError> {
+    let reports = ReportPart1::from(input)?;
 
-    reports
+    Ok(reports
         .iter()
         .map(|r| r.is_safe().then(|| 1).or_else(|| Some(0)).unwrap())
-        .sum()
+        .sum())
 }
 
-struct ReportPart2 {
-    values: Vec<u16>,
+const MIN_STEP: u16 = 1;
+const MAX_STEP: u16 = 3;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ReportDirection {
+    Ascending,
+    Descending,
 }
 
-impl ReportPart2 {
-    fn from(input: &str) -> Vec<ReportPart2> {
-        input
-            .lines()
-            .map(|l| ReportPart2 {
-                values: l
-                    .split_whitespace()
-                    .map(|n| n.parse::<u16>().unwrap())
-                    .collect(),
-            })
-            .collect()
-    }
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ReportViolationKind {
+    DirectionChange,
+    StepOutOfRange,
+}
 
-    fn get_prev_i(item_removed: Option<usize>, i: usize) -> Option<usize> {
-        match item_removed {
-            Option::Some(i2) if i2 == 0 => None,
-            Option::Some(i2) if i2 == (i - 1) => Some(i2 - 1),
-            _ if i == 0 => None,
-            _ => Some(i - 1),
+impl ReportViolationKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ReportViolationKind::DirectionChange => "direction_change",
+            ReportViolationKind::StepOutOfRange => "step_out_of_range",
         }
     }
+}
 
-    fn get_next_next_i(&self, i: usize) -> Option<usize> {
-        if i + 2 == self.values.len() {
-            None
-        } else {
-            Some(i + 2)
-        }
+#[derive(Debug, PartialEq)]
+struct ReportViolation {
+    kind: ReportViolationKind,
+    indices: (usize, usize),
+    values: (u16, u16),
+}
+
+#[derive(Debug, PartialEq)]
+enum ReportVerdict {
+    Safe { removed_indices: Vec<usize> },
+    Unsafe,
+}
+
+impl ReportVerdict {
+    fn is_safe(&self) -> bool {
+        matches!(self, ReportVerdict::Safe { .. })
     }
+}
 
-    fn test_values(&self, compare_values: fn(v1: &u16, v2: &u16) -> bool) -> bool {
-        if self.values.len() <= 1 {
-            return true;
+struct ReportChecker {
+    max_removals: usize,
+    min_step: u16,
+    max_step: u16,
+}
+
+impl ReportChecker {
+    fn new(max_removals: usize, min_step: u16, max_step: u16) -> ReportChecker {
+        ReportChecker {
+            max_removals,
+            min_step,
+            max_step,
         }
+    }
 
-        let mut item_removed: Option<usize> = Option::None;
+    fn is_valid_step(&self, direction: ReportDirection, v1: u16, v2: u16) -> bool {
+        let is_in_direction = match direction {
+            ReportDirection::Ascending => v1 <= v2,
+            ReportDirection::Descending => v1 >= v2,
+        };
 
-        for i in 0..self.values.len() - 1 {
-            if item_removed.is_some_and(|item_removed_i| i == item_removed_i) {
-                continue;
+        is_in_direction && (self.min_step..=self.max_step).contains(&v1.abs_diff(v2))
+    }
+
+    // finds the fewest removals (up to max_removals) that leave every adjacent pair stepping in `direction`.
+    // removals[i] is the fewest removals needed before i when i is kept, and since more than max_removals values
+    // can never be skipped, i only has to look back max_removals + 1 values, which keeps this O(n * k).
+    fn fewest_removals(&self, values: &[u16], direction: ReportDirection) -> Option<Vec<usize>> {
+        let mut removals: Vec<Option<usize>> = vec![None; values.len()];
+        let mut prev_kept_i: Vec<Option<usize>> = vec![None; values.len()];
+
+        for i in 0..values.len() {
+            if i <= self.max_removals {
+                removals[i] = Some(i);
             }
 
-            let prev_i = ReportPart2::get_prev_i(item_removed, i);
-            let next_i = i + 1;
-            let next_next_i = self.get_next_next_i(i);
+            for j in i.saturating_sub(self.max_removals + 1)..i {
+                let Some(removals_before_j) = removals[j] else {
+                    continue;
+                };
 
-            let prev = prev_i.and_then(|p_i| self.values.get(p_i));
-            let cur = self.values.get(i).unwrap();
-            let next = self.values.get(next_i).unwrap();
-            let next_next = next_next_i.and_then(|nn_i| self.values.get(nn_i));
+                let num_removals = removals_before_j + (i - j - 1);
 
-            if !(compare_values(cur, next) && ReportPart2::is_within_range(cur, next)) {
-                if item_removed.is_some() {
-                    return false;
+                if num_removals <= self.max_removals
+                    && removals[i].is_none_or(|r| num_removals < r)
+                    && self.is_valid_step(direction, values[j], values[i])
+                {
+                    removals[i] = Some(num_removals);
+                    prev_kept_i[i] = Some(j);
                 }
+            }
+        }
 
-                let is_at_end_of_list = i == self.values.len() - 2;
+        let last_kept_i = (0..values.len())
+            .filter_map(|i| removals[i].map(|r| (i, r + (values.len() - 1 - i))))
+            .filter(|(_, num_removals)| *num_removals <= self.max_removals)
+            .min_by_key(|(_, num_removals)| *num_removals)
+            .map(|(i, _)| i);
 
-                if is_at_end_of_list {
-                    return true;
-                }
+        let Some(last_kept_i) = last_kept_i else {
+            return values.is_empty().then(Vec::new);
+        };
 
-                // if removing cur
+        let mut is_kept = vec![false; values.len()];
+        let mut kept_i = Some(last_kept_i);
 
-                let is_previous_and_next_valid = prev.is_none()
-                    || prev.is_some_and(|p| {
-                        compare_values(p, next) && ReportPart2::is_within_range(p, next)
-                    });
-                let is_next_and_next_next_valid = next_next.is_some_and(|nn| {
-                    compare_values(next, nn) && ReportPart2::is_within_range(next, nn)
-                });
+        while let Some(i) = kept_i {
+            is_kept[i] = true;
+            kept_i = prev_kept_i[i];
+        }
 
-                if is_previous_and_next_valid && is_next_and_next_next_valid {
-                    item_removed = Some(i);
-                    continue;
-                }
+        Some((0..values.len()).filter(|i| !is_kept[*i]).collect())
+    }
 
-                // if removing next
-                let is_cur_and_next_next_valid = next_next.is_some_and(|nn| {
-                    compare_values(cur, nn) && ReportPart2::is_within_range(cur, nn)
-                });
+    fn check(&self, values: &[u16]) -> ReportVerdict {
+        let ascending = self.fewest_removals(values, ReportDirection::Ascending);
+        let descending = self.fewest_removals(values, ReportDirection::Descending);
 
-                if is_cur_and_next_next_valid {
-                    item_removed = Some(i + 1);
-                    continue;
+        let removed_indices = match (ascending, descending) {
+            (Some(a), Some(d)) if d.len() < a.len() => Some(d),
+            (Some(a), _) => Some(a),
+            (None, d) => d,
+        };
+
+        match removed_indices {
+            Some(removed_indices) => ReportVerdict::Safe { removed_indices },
+            None => ReportVerdict::Unsafe,
+        }
+    }
+
+    fn first_violation(&self, values: &[u16]) -> Option<ReportViolation> {
+        let mut report_direction: Option<ReportDirection> = None;
+
+        for i in 0..values.len().saturating_sub(1) {
+            let (v1, v2) = (values[i], values[i + 1]);
+
+            let kind = if !(self.min_step..=self.max_step).contains(&v1.abs_diff(v2)) {
+                Some(ReportViolationKind::StepOutOfRange)
+            } else {
+                let pair_direction = match v1.cmp(&v2) {
+                    Ordering::Less => Some(ReportDirection::Ascending),
+                    Ordering::Greater => Some(ReportDirection::Descending),
+                    Ordering::Equal => None,
+                };
+
+                match (report_direction, pair_direction) {
+                    (Some(d1), Some(d2)) if d1 != d2 => Some(ReportViolationKind::DirectionChange),
+                    (None, Some(_)) => {
+                        report_direction = pair_direction;
+                        None
+                    }
+                    _ => None,
                 }
+            };
 
-                return false;
+            if let Some(kind) = kind {
+                return Some(ReportViolation {
+                    kind,
+                    indices: (i, i + 1),
+                    values: (v1, v2),
+                });
             }
         }
 
-        return true;
+        None
     }
 
-    fn is_ascending(v1: &u16, v2: &u16) -> bool {
-        *v1 < *v2
+    fn explain(&self, values: &[u16]) -> ReportExplanation {
+        ReportExplanation {
+            values: values.to_vec(),
+            min_step: self.min_step,
+            max_step: self.max_step,
+            violation: self.first_violation(values),
+            verdict: self.check(values),
+        }
     }
+}
 
-    fn is_descending(v1: &u16, v2: &u16) -> bool {
-        *v1 > *v2
-    }
+fn parse_reports(input: &str) -> Result<Vec<Vec<u16>>, InputError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(line_i, l)| {
+            l.split_whitespace()
+                .map(|n| parse_number::<u16>(n, line_i))
+                .collect()
+        })
+        .collect()
+}
+
+#[aoc(day2, part2)]
+fn part2(input: &str) -> Result<u32, InputError> {
+    let reports = parse_reports(input)?;
+    let report_checker = ReportChecker::new(1, MIN_STEP, MAX_STEP);
+
+    Ok(reports
+        .iter()
+        .filter(|r| report_checker.check(r).is_safe())
+        .count() as u32)
+}
+
+fn remove_indices(values: &[u16], removed_indices: &[usize]) -> Vec<u16> {
+    values
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !removed_indices.contains(i))
+        .map(|(_, v)| *v)
+        .collect()
+}
+
+// safe exactly as the puzzle words it: all increasing or all decreasing, by at least one and at most three
+fn is_safe_brute_force(levels: &[u16]) -> bool {
+    let steps: Vec<i32> = levels
+        .windows(2)
+        .map(|w| w[1] as i32 - w[0] as i32)
+        .collect();
+
+    steps.iter().all(|step| (1..=3).contains(step))
+        || steps.iter().all(|step| (-3..=-1).contains(step))
+}
+
+#[aoc(day2, part1, BruteForce)]
+fn part1_brute_force(input: &str) -> Result<u32, InputError> {
+    Ok(parse_reports(input)?
+        .iter()
+        .filter(|levels| is_safe_brute_force(levels))
+        .count() as u32)
+}
+
+// the dampener tries removing every level in turn
+#[aoc(day2, part2, BruteForce)]
+fn part2_brute_force(input: &str) -> Result<u32, InputError> {
+    Ok(parse_reports(input)?
+        .iter()
+        .filter(|levels| {
+            is_safe_brute_force(levels)
+                || (0..levels.len()).any(|i| is_safe_brute_force(&remove_indices(levels, &[i])))
+        })
+        .count() as u32)
+}
 
-    fn is_within_range(v1: &u16, v2: &u16) -> bool {
-        const MIN: u16 = 1;
-        const MAX: u16 = 3;
+pub struct ReportExplanation {
+    values: Vec<u16>,
+    min_step: u16,
+    max_step: u16,
+    violation: Option<ReportViolation>,
+    verdict: ReportVerdict,
+}
 
-        let abs_diff: u16 = (*v1).abs_diff(*v2);
-        MIN <= abs_diff && abs_diff <= MAX
+impl ReportExplanation {
+    pub fn is_safe(&self) -> bool {
+        self.verdict.is_safe()
     }
 
-    fn is_safe(&self) -> bool {
-        self.test_values(ReportPart2::is_ascending) || self.test_values(ReportPart2::is_descending)
+    pub fn to_json_line(&self) -> String {
+        let join = |values: &mut dyn Iterator<Item = String>| values.collect::<Vec<_>>().join(",");
+
+        let verdict = match &self.verdict {
+            ReportVerdict::Safe { removed_indices } if removed_indices.is_empty() => "safe",
+            ReportVerdict::Safe { .. } => "safe_with_removals",
+            ReportVerdict::Unsafe => "unsafe",
+        };
+
+        let violation = match &self.violation {
+            Some(violation) => format!(
+                r#"{{"kind":"{}","indices":[{},{}],"values":[{},{}]}}"#,
+                violation.kind.as_str(),
+                violation.indices.0,
+                violation.indices.1,
+                violation.values.0,
+                violation.values.1
+            ),
+            None => String::from("null"),
+        };
+
+        let removed_indices = match &self.verdict {
+            ReportVerdict::Safe { removed_indices } => {
+                format!(
+                    "[{}]",
+                    join(&mut removed_indices.iter().map(|i| i.to_string()))
+                )
+            }
+            ReportVerdict::Unsafe => String::from("null"),
+        };
+
+        format!(
+            r#"{{"values":[{}],"verdict":"{}","violation":{},"removed_indices":{}}}"#,
+            join(&mut self.values.iter().map(|v| v.to_string())),
+            verdict,
+            violation,
+            removed_indices
+        )
     }
 }
 
-#[aoc(day2, part2)]
-fn part2(input: &str) -> u32 {
-    let reports = ReportPart2::from(input);
+impl fmt::Display for ReportExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let values: Vec<String> = self.values.iter().map(|v| v.to_string()).collect();
+
+        match &self.verdict {
+            ReportVerdict::Safe { removed_indices } if removed_indices.is_empty() => {
+                write!(f, "[{}] safe", values.join(" "))?;
+            }
+            ReportVerdict::Safe { removed_indices } => {
+                let removed: Vec<String> = removed_indices
+                    .iter()
+                    .map(|i| format!("index {} ({})", i, self.values[*i]))
+                    .collect();
+                write!(
+                    f,
+                    "[{}] safe after removing {}",
+                    values.join(" "),
+                    removed.join(", ")
+                )?;
+            }
+            ReportVerdict::Unsafe => {
+                write!(f, "[{}] unsafe", values.join(" "))?;
+            }
+        }
+
+        if let Some(violation) = &self.violation {
+            let (i1, i2) = violation.indices;
+            let (v1, v2) = violation.values;
+
+            match violation.kind {
+                ReportViolationKind::DirectionChange => write!(
+                    f,
+                    "; first violation: direction changes at indices {i1}, {i2} ({v1} -> {v2})"
+                )?,
+                ReportViolationKind::StepOutOfRange => write!(
+                    f,
+                    "; first violation: step of {} at indices {i1}, {i2} ({v1} -> {v2}) is outside {}..={}",
+                    v1.abs_diff(v2),
+                    self.min_step,
+                    self.max_step
+                )?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn explain_reports(
+    input: &str,
+    max_removals: usize,
+) -> Result<Vec<ReportExplanation>, InputError> {
+    let report_checker = ReportChecker::new(max_removals, MIN_STEP, MAX_STEP);
 
-    reports
+    Ok(parse_reports(input)?
         .iter()
-        .map(|r| r.is_safe().then(|| 1).or_else(|| Some(0)).unwrap())
-        .sum()
+        .map(|r| report_checker.explain(r))
+        .collect())
+}
+
+/// A puzzle input of `num_reports` reports of 5 to 8 levels. About half of the reports are made unsafe by up to two
+/// levels that repeat, jump or turn back, so both the dampener and the plain check have something to do.
+pub fn generate_input(seed: u64, num_reports: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let reports: Vec<String> = (0..num_reports)
+        .map(|_| {
+            let len = rng.random_range(5..=8);
+            let direction = if rng.random_bool(0.5) { 1 } else { -1 };
+            let num_faults = if rng.random_bool(0.5) {
+                rng.random_range(1..=2)
+            } else {
+                0
+            };
+            let fault_indices: Vec<usize> =
+                (0..num_faults).map(|_| rng.random_range(1..len)).collect();
+
+            let mut level: i32 = rng.random_range(45..=55);
+            let mut levels = vec![level];
+
+            for i in 1..len {
+                let step = if fault_indices.contains(&i) {
+                    match rng.random_range(0..3) {
+                        0 => 0,
+                        1 => direction * rng.random_range(4..=6),
+                        _ => -direction * rng.random_range(1..=3),
+                    }
+                } else {
+                    direction * rng.random_range(MIN_STEP as i32..=MAX_STEP as i32)
+                };
+
+                level = (level + step).max(1);
+                levels.push(level);
+            }
+
+            levels
+                .iter()
+                .map(|level| level.to_string())
+                .collect::<Vec<String>>()
+                .join(" ")
+        })
+        .collect();
+
+    reports.join("\n")
+}
+
+/// A synthetic input with as many reports as `input`, for `aoc synthesize`
+pub fn synthesize(input: &str, seed: u64) -> Result<String, Box<dyn Error>> {
+    Ok(generate_input(seed, parse_reports(input)?.len()))
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "7 6 4 2 1
+1 2 7 8 9
+9 7 6 2 1
+1 3 2 4 5
+8 6 4 4 1
+1 3 6 7 9";
+
+    fn is_valid_report(values: &[u16], min_step: u16, max_step: u16) -> bool {
+        let checker = ReportChecker::new(0, min_step, max_step);
+
+        values
+            .windows(2)
+            .all(|w| checker.is_valid_step(ReportDirection::Ascending, w[0], w[1]))
+            || values
+                .windows(2)
+                .all(|w| checker.is_valid_step(ReportDirection::Descending, w[0], w[1]))
+    }
+
+    fn combinations(n: usize, size: usize) -> Vec<Vec<usize>> {
+        if size == 0 {
+            return vec![Vec::new()];
+        }
+
+        (0..n)
+            .flat_map(|last| {
+                combinations(last, size - 1).into_iter().map(move |mut c| {
+                    c.push(last);
+                    c
+                })
+            })
+            .collect()
+    }
+
+    // fewest removals found by trying every subset of up to max_removals indices
+    fn brute_force_fewest_removals(
+        values: &[u16],
+        max_removals: usize,
+        min_step: u16,
+        max_step: u16,
+    ) -> Option<usize> {
+        (0..=max_removals.min(values.len())).find(|size| {
+            combinations(values.len(), *size)
+                .iter()
+                .any(|removed_indices| {
+                    is_valid_report(&remove_indices(values, removed_indices), min_step, max_step)
+                })
+        })
+    }
+
+    #[test]
+    fn test_parts_match_brute_force_on_generated_inputs() {
+        for seed in 0..2_000 {
+            let input = generate_input(seed, 20);
+
+            assert_eq!(part1(&input), part1_brute_force(&input), "seed {seed}");
+            assert_eq!(part2(&input), part2_brute_force(&input), "seed {seed}");
+        }
+    }
+
+    #[test]
+    fn test_generate_input() {
+        let input = generate_input(7, 1_000);
+
+        assert_eq!(input, generate_input(7, 1_000));
+        assert_eq!(input.lines().count(), 1_000);
+
+        // most generated reports are neither all safe nor all hopeless
+        let (safe, dampened) = (
+            part1_brute_force(&input).unwrap(),
+            part2_brute_force(&input).unwrap(),
+        );
+        assert!(
+            300 < safe && safe < dampened && dampened < 900,
+            "{safe} {dampened}"
+        );
+    }
+
+    #[test]
+    fn test_example() {
+        assert_eq!(part1(EXAMPLE), Ok(2));
+        assert_eq!(part2(EXAMPLE), Ok(4));
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        assert_eq!(
+            part1("1 2 3\n4 x 6").unwrap_err().to_string(),
+            "line 2: \"x\" is not a valid number"
+        );
+        assert!(part2("1 2 70000").is_err());
+    }
+
+    #[test]
+    fn test_check_returns_removed_indices() {
+        let checker = ReportChecker::new(2, MIN_STEP, MAX_STEP);
+
+        assert_eq!(
+            checker.check(&[1, 2, 3, 4]),
+            ReportVerdict::Safe {
+                removed_indices: vec![]
+            }
+        );
+        assert_eq!(
+            checker.check(&[9, 7, 20, 6, 5]),
+            ReportVerdict::Safe {
+                removed_indices: vec![2]
+            }
+        );
+        assert_eq!(
+            checker.check(&[1, 2, 9, 9, 3]),
+            ReportVerdict::Safe {
+                removed_indices: vec![2, 3]
+            }
+        );
+        assert_eq!(checker.check(&[1, 2, 2, 2, 2, 3]), ReportVerdict::Unsafe);
+    }
+
+    #[test]
+    fn test_check_matches_brute_force() {
+        const MAX_VALUE: u16 = 4;
+        const MAX_LEN: u32 = 6;
+
+        for (min_step, max_step) in [(MIN_STEP, MAX_STEP), (0, 2), (2, 2)] {
+            for len in 0..=MAX_LEN {
+                for n in 0..(MAX_VALUE as usize + 1).pow(len) {
+                    let values: Vec<u16> = (0..len)
+                        .map(|d| {
+                            (n / (MAX_VALUE as usize + 1).pow(d) % (MAX_VALUE as usize + 1)) as u16
+                        })
+                        .collect();
+
+                    for max_removals in 0..=3 {
+                        let checker = ReportChecker::new(max_removals, min_step, max_step);
+                        let expected =
+                            brute_force_fewest_removals(&values, max_removals, min_step, max_step);
+
+                        match checker.check(&values) {
+                            ReportVerdict::Safe { removed_indices } => {
+                                assert_eq!(Some(removed_indices.len()), expected, "{values:?}");
+                                assert!(is_valid_report(
+                                    &remove_indices(&values, &removed_indices),
+                                    min_step,
+                                    max_step
+                                ));
+                            }
+                            ReportVerdict::Unsafe => assert_eq!(expected, None, "{values:?}"),
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_check_long_report() {
+        let mut values: Vec<u16> = (0..20_000).collect();
+        values[500] = 0;
+        values[7_000] = 9_000;
+        values[19_999] = 1;
+
+        let checker = ReportChecker::new(3, MIN_STEP, MAX_STEP);
+
+        assert_eq!(
+            checker.check(&values),
+            ReportVerdict::Safe {
+                removed_indices: vec![500, 7_000, 19_999]
+            }
+        );
+        assert_eq!(
+            ReportChecker::new(2, MIN_STEP, MAX_STEP).check(&values),
+            ReportVerdict::Unsafe
+        );
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanations = explain_reports(EXAMPLE, 1).unwrap();
+
+        assert_eq!(
+            explanations
+                .iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<String>>(),
+            vec![
+                "[7 6 4 2 1] safe",
+                "[1 2 7 8 9] unsafe; first violation: step of 5 at indices 1, 2 (2 -> 7) is outside 1..=3",
+                "[9 7 6 2 1] unsafe; first violation: step of 4 at indices 2, 3 (6 -> 2) is outside 1..=3",
+                "[1 3 2 4 5] safe after removing index 2 (2); first violation: direction changes at indices 1, 2 (3 -> 2)",
+                "[8 6 4 4 1] safe after removing index 3 (4); first violation: step of 0 at indices 2, 3 (4 -> 4) is outside 1..=3",
+                "[1 3 6 7 9] safe",
+            ]
+        );
+
+        assert_eq!(
+            explanations[0].to_json_line(),
+            r#"{"values":[7,6,4,2,1],"verdict":"safe","violation":null,"removed_indices":[]}"#
+        );
+        assert_eq!(
+            explanations[1].to_json_line(),
+            r#"{"values":[1,2,7,8,9],"verdict":"unsafe","violation":{"kind":"step_out_of_range","indices":[1,2],"values":[2,7]},"removed_indices":null}"#
+        );
+        assert_eq!(
+            explanations[3].to_json_line(),
+            r#"{"values":[1,3,2,4,5],"verdict":"safe_with_removals","violation":{"kind":"direction_change","indices":[1,2],"values":[3,2]},"removed_indices":[2]}"#
+        );
+    }
+}
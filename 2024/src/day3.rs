@@ -1,24 +1,34 @@
 use aoc_runner_derive::aoc;
+use aoc_tools::answer::{Answer, Overflow, checked_add, checked_sum};
+use rand::{Rng, SeedableRng, rngs::StdRng};
 use regex::{CaptureMatches, Captures, Regex};
-use std::sync::LazyLock;
+use std::{
+    error::Error,
+    io::{self, ErrorKind, Read},
+    sync::LazyLock,
+};
+
+// [0-9] rather than \d, which also matches digits of other scripts that do not parse as a u64
+const MUL_PATTERN: &str = r"mul\((?<x>[0-9]{1,3}),(?<y>[0-9]{1,3})\)";
 
 #[aoc(day3, part1)]
-fn part1(input: &str) -> u32 {
+fn part1(input: &str) -> Result<Answer, Overflow> {
     let mut count = 0;
 
-    let mult_regex = Regex::new(r"mul\((?<x>\d{1,3}),(?<y>\d{1,3})\)").unwrap();
+    let mult_regex = Regex::new(MUL_PATTERN).unwrap();
 
-    mult_regex
+    let products = mult_regex
         .captures_iter(input)
         .map(|capture| capture.extract())
         .map(|(_, [x, y])| {
             count = count + 1;
-            let x = x.parse::<u32>().unwrap();
-            let y = y.parse::<u32>().unwrap();
+            let x = x.parse::<u64>().unwrap();
+            let y = y.parse::<u64>().unwrap();
 
             x * y
-        })
-        .sum::<u32>()
+        });
+
+    Ok(checked_sum(products)?.into())
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -95,19 +105,24 @@ impl<'i> DoDontMarkerIterator<'i> {
 
     fn get_marker(&mut self, position: &usize) -> DoDontMarker {
         if self.cur_marker.is_none() {
-            let first_marker = self
-                .next_marker
-                .get_or_insert_with(|| DoDontMarker::new(self.iter.next()).unwrap());
-
-            if first_marker.is_before(position) {
-                self.cur_marker = Some(first_marker.clone());
+            if self.next_marker.is_none() {
                 self.next_marker = DoDontMarker::new(self.iter.next());
-            } else {
+            }
+
+            // multiplications before the first marker, or in a program without any, are enabled
+            let Some(first_marker) = self
+                .next_marker
+                .as_ref()
+                .filter(|first_marker| first_marker.is_before(position))
+            else {
                 return DoDontMarker {
                     start_position: 0,
                     marker_type: DoDontMarkerType::DO,
                 };
-            }
+            };
+
+            self.cur_marker = Some(first_marker.clone());
+            self.next_marker = DoDontMarker::new(self.iter.next());
         }
 
         let cur_marker = self.cur_marker.as_ref().unwrap();
@@ -121,57 +136,292 @@ impl<'i> DoDontMarkerIterator<'i> {
 
         let mut next_marker = self.next_marker.as_ref().unwrap().clone();
 
-        while next_marker.is_after(position) {
+        // skip over every marker between the previous position and this one; only the last one applies
+        loop {
             let next_next_marker = DoDontMarker::new(self.iter.next());
 
-            if next_next_marker.is_none() {
+            if next_next_marker
+                .as_ref()
+                .is_none_or(|next_next_marker| next_next_marker.is_after(position))
+            {
                 self.cur_marker = Some(next_marker.clone());
-                self.next_marker = None;
-                return self.cur_marker.as_ref().unwrap().clone();
+                self.next_marker = next_next_marker;
+                return next_marker;
             }
 
-            next_marker = next_next_marker.unwrap().clone();
+            next_marker = next_next_marker.unwrap();
+        }
+    }
+}
+
+#[aoc(day3, part2)]
+fn part2(input: &str) -> Result<Answer, Overflow> {
+    let mut do_dont_iter = DoDontMarkerIterator::new(input);
+
+    let mult_regex = Regex::new(MUL_PATTERN).unwrap();
+
+    let products = mult_regex.captures_iter(input).map(|capture| {
+        let capture_x = capture.name("x").unwrap();
+        let capture_y = capture.name("y").unwrap();
+
+        let x = capture_x.as_str().parse::<u64>().unwrap();
+        let y = capture_y.as_str().parse::<u64>().unwrap();
+
+        let position = capture_x.start();
+
+        let do_dont_marker = do_dont_iter.get_marker(&position);
+
+        if do_dont_marker.marker_type == DoDontMarkerType::DO {
+            return x * y;
+        } else {
+            return 0;
         }
+    });
 
-        self.cur_marker = Some(next_marker.clone());
+    Ok(checked_sum(products)?.into())
+}
 
-        self.next_marker = DoDontMarker::new(self.iter.next());
+#[derive(Clone, Copy)]
+enum MulScannerState {
+    Start,
+    M,
+    Mu,
+    Mul,
+    X { num_digits: u8 },
+    Y { num_digits: u8 },
+}
 
-        return self.cur_marker.as_ref().unwrap().clone();
+// byte-at-a-time equivalent of `mul\((?<x>[0-9]{1,3}),(?<y>[0-9]{1,3})\)`; only `m` can start a match and it never
+// appears inside one, so on a mismatch it is enough to re-check the current byte from the start
+struct MulScanner {
+    state: MulScannerState,
+    x: u32,
+    y: u32,
+}
+
+impl MulScanner {
+    fn new() -> MulScanner {
+        MulScanner {
+            state: MulScannerState::Start,
+            x: 0,
+            y: 0,
+        }
+    }
+
+    fn next_state(&mut self, byte: u8) -> Option<MulScannerState> {
+        match (self.state, byte) {
+            (MulScannerState::Start, b'm') => Some(MulScannerState::M),
+            (MulScannerState::M, b'u') => Some(MulScannerState::Mu),
+            (MulScannerState::Mu, b'l') => Some(MulScannerState::Mul),
+            (MulScannerState::Mul, b'(') => {
+                self.x = 0;
+                Some(MulScannerState::X { num_digits: 0 })
+            }
+            (MulScannerState::X { num_digits }, b'0'..=b'9') if num_digits < 3 => {
+                self.x = self.x * 10 + (byte - b'0') as u32;
+                Some(MulScannerState::X {
+                    num_digits: num_digits + 1,
+                })
+            }
+            (MulScannerState::X { num_digits }, b',') if num_digits > 0 => {
+                self.y = 0;
+                Some(MulScannerState::Y { num_digits: 0 })
+            }
+            (MulScannerState::Y { num_digits }, b'0'..=b'9') if num_digits < 3 => {
+                self.y = self.y * 10 + (byte - b'0') as u32;
+                Some(MulScannerState::Y {
+                    num_digits: num_digits + 1,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn next(&mut self, byte: u8) -> Option<u32> {
+        if let MulScannerState::Y { num_digits } = self.state
+            && num_digits > 0
+            && byte == b')'
+        {
+            self.state = MulScannerState::Start;
+            return Some(self.x * self.y);
+        }
+
+        self.state = match self.next_state(byte) {
+            Some(state) => state,
+            None => {
+                self.state = MulScannerState::Start;
+                self.next_state(byte).unwrap_or(MulScannerState::Start)
+            }
+        };
+
+        None
     }
 }
 
-#[aoc(day3, part2)]
-fn part2(input: &str) -> u32 {
-    let mut do_dont_iter = DoDontMarkerIterator::new(input);
+#[derive(Clone, Copy)]
+enum DoDontScannerState {
+    Start,
+    D,
+    Do,
+    DoOpen,
+    Don,
+    DonApostrophe,
+    Dont,
+    DontOpen,
+}
 
-    let mult_regex = Regex::new(r"mul\((?<x>\d{1,3}),(?<y>\d{1,3})\)").unwrap();
+// byte-at-a-time equivalent of `DO_DONT_REGEX`; like `MulScanner`, only `d` can start a match
+struct DoDontScanner {
+    state: DoDontScannerState,
+}
 
-    mult_regex
-        .captures_iter(input)
-        .map(|capture| {
-            let capture_x = capture.name("x").unwrap();
-            let capture_y = capture.name("y").unwrap();
+impl DoDontScanner {
+    fn new() -> DoDontScanner {
+        DoDontScanner {
+            state: DoDontScannerState::Start,
+        }
+    }
+
+    fn next_state(&self, byte: u8) -> Option<DoDontScannerState> {
+        match (self.state, byte) {
+            (DoDontScannerState::Start, b'd') => Some(DoDontScannerState::D),
+            (DoDontScannerState::D, b'o') => Some(DoDontScannerState::Do),
+            (DoDontScannerState::Do, b'(') => Some(DoDontScannerState::DoOpen),
+            (DoDontScannerState::Do, b'n') => Some(DoDontScannerState::Don),
+            (DoDontScannerState::Don, b'\'') => Some(DoDontScannerState::DonApostrophe),
+            (DoDontScannerState::DonApostrophe, b't') => Some(DoDontScannerState::Dont),
+            (DoDontScannerState::Dont, b'(') => Some(DoDontScannerState::DontOpen),
+            _ => None,
+        }
+    }
+
+    fn next(&mut self, byte: u8) -> Option<DoDontMarkerType> {
+        match (self.state, byte) {
+            (DoDontScannerState::DoOpen, b')') => {
+                self.state = DoDontScannerState::Start;
+                return Some(DoDontMarkerType::DO);
+            }
+            (DoDontScannerState::DontOpen, b')') => {
+                self.state = DoDontScannerState::Start;
+                return Some(DoDontMarkerType::DONT);
+            }
+            _ => {}
+        }
 
-            let x = capture_x.as_str().parse::<u32>().unwrap();
-            let y = capture_y.as_str().parse::<u32>().unwrap();
+        self.state = match self.next_state(byte) {
+            Some(state) => state,
+            None => {
+                self.state = DoDontScannerState::Start;
+                self.next_state(byte).unwrap_or(DoDontScannerState::Start)
+            }
+        };
 
-            let position = capture_x.start();
+        None
+    }
+}
 
-            let do_dont_marker = do_dont_iter.get_marker(&position);
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+// evaluates a program read in `chunk_size` byte chunks, so memory use stays bounded no matter how large the
+// program is. tokens split across chunk boundaries are carried over in the scanners' state.
+pub fn evaluate_stream<R: Read>(
+    mut reader: R,
+    chunk_size: usize,
+    use_do_dont_markers: bool,
+) -> io::Result<Answer> {
+    let mut chunk = vec![0; chunk_size.max(1)];
+
+    let mut mul_scanner = MulScanner::new();
+    let mut do_dont_scanner = DoDontScanner::new();
+    let mut marker_type = DoDontMarkerType::DO;
+
+    // a stress input of a few megabytes already adds up to more than a u32 holds
+    let mut sum: u64 = 0;
+
+    loop {
+        let num_bytes_read = match reader.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(num_bytes_read) => num_bytes_read,
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+
+        for byte in &chunk[..num_bytes_read] {
+            if use_do_dont_markers && let Some(new_marker_type) = do_dont_scanner.next(*byte) {
+                marker_type = new_marker_type;
+            }
 
-            if do_dont_marker.marker_type == DoDontMarkerType::DO {
-                return x * y;
-            } else {
-                return 0;
+            if let Some(product) = mul_scanner.next(*byte)
+                && marker_type == DoDontMarkerType::DO
+            {
+                sum = checked_add(sum, u64::from(product)).map_err(io::Error::other)?;
             }
-        })
-        .sum::<u32>()
+        }
+    }
+
+    Ok(sum.into())
+}
+
+#[aoc(day3, part1, Streaming)]
+fn part1_streaming(input: &[u8]) -> io::Result<Answer> {
+    evaluate_stream(input, DEFAULT_CHUNK_SIZE, false)
+}
+
+#[aoc(day3, part2, Streaming)]
+fn part2_streaming(input: &[u8]) -> io::Result<Answer> {
+    evaluate_stream(input, DEFAULT_CHUNK_SIZE, true)
+}
+
+/// A corrupted memory of `num_chars` characters: `mul` instructions of one to three digit numbers, `do()` and `don't()`
+/// markers, instructions broken in the ways the puzzle warns about, and junk in between
+pub fn generate_input(seed: u64, num_chars: usize) -> String {
+    const JUNK: &[u8] = b"!@#$%^&*()[]{}<>?+-_,;:' whatfromselectwhywhenhow";
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut memory = String::new();
+
+    while memory.len() < num_chars {
+        let (x, y) = (rng.random_range(1..1000), rng.random_range(1..1000));
+
+        let fragment = match rng.random_range(0..10) {
+            0..3 => format!("mul({x},{y})"),
+            3 if rng.random_bool(0.5) => String::from("do()"),
+            3 => String::from("don't()"),
+            4 => match rng.random_range(0..4) {
+                0 => format!("mul[{x},{y}]"),
+                1 => format!("mul({x},{y}"),
+                2 => format!("mul( {x},{y})"),
+                _ => format!("mul({},{y})", x * 10 + 1),
+            },
+            _ => (0..rng.random_range(1..8))
+                .map(|_| JUNK[rng.random_range(0..JUNK.len())] as char)
+                .collect(),
+        };
+
+        memory += &fragment;
+    }
+
+    memory.truncate(num_chars);
+    memory
+}
+
+/// A synthetic input with lines as long as those of `input`, for `aoc synthesize`
+pub fn synthesize(input: &str, seed: u64) -> Result<String, Box<dyn Error>> {
+    Ok(input
+        .lines()
+        .enumerate()
+        .map(|(line_i, line)| generate_input(seed.wrapping_add(line_i as u64), line.len()))
+        .collect::<Vec<String>>()
+        .join("\n"))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day3::part1;
+    use aoc_tools::answer::Answer;
+
+    use regex::Regex;
+
+    use crate::day3::{MUL_PATTERN, evaluate_stream, generate_input, part1, part2};
 
     #[test]
     fn test() {
@@ -180,7 +430,118 @@ mod tests {
                 "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))
             xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))"
             ),
-            161 + 161
+            Ok(Answer::from(161 + 161))
         )
     }
+
+    const PART2_EXAMPLE: &str =
+        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+    const STRESS_INPUT: &str = "mul(1,2)mul(12,345)mmul(999,999)mul(1234,5)mul(4,)mul(,4)mul( 1,2)\
+        don't()mul(7,7)dodon't()do()don't()do()mul(3,3)don't(do()mul(10,10)don'tmul(2,2)\
+        mul(6,6)don't()don't()mul(8,8)dmul(1,1)do()do(mul(5,50)";
+
+    #[test]
+    fn test_parts_match_streaming_on_generated_inputs() {
+        for seed in 0..200 {
+            let input = generate_input(seed, 500);
+
+            assert_eq!(input.len(), 500);
+            assert_eq!(
+                part1(&input).unwrap(),
+                evaluate_stream(input.as_bytes(), 64, false).unwrap(),
+                "seed {seed}"
+            );
+            assert_eq!(
+                part2(&input).unwrap(),
+                evaluate_stream(input.as_bytes(), 64, true).unwrap(),
+                "seed {seed}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parts_past_u32() {
+        let input = "mul(999,999)".repeat(5_000);
+
+        assert_eq!(part1(&input), Ok(Answer::from(5_000 * 998_001_u64)));
+        assert_eq!(part2(&input), Ok(Answer::from(5_000 * 998_001_u64)));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(PART2_EXAMPLE), Ok(Answer::from(48)));
+    }
+
+    #[test]
+    fn test_no_markers_or_foreign_digits() {
+        assert_eq!(part2("mul(2,3)xmul(4,5)"), Ok(Answer::from(26)));
+        assert_eq!(part2("mul(\u{661},2)"), Ok(Answer::from(0)));
+        assert_eq!(part1("mul(\u{661},2)mul(1,2)"), Ok(Answer::from(2)));
+    }
+
+    #[test]
+    fn test_part2_marker_runs() {
+        assert_eq!(
+            part2("mul(1,1)don't()do()don't()mul(2,2)do()mul(3,3)"),
+            Ok(Answer::from(1 + 9))
+        );
+    }
+
+    #[test]
+    fn test_evaluate_stream_matches_in_memory_for_every_chunk_size() {
+        for input in [PART2_EXAMPLE, STRESS_INPUT] {
+            let expected_part1 = part1(input).unwrap();
+            let expected_part2 = part2(input).unwrap();
+
+            for chunk_size in 1..=input.len() + 1 {
+                assert_eq!(
+                    evaluate_stream(input.as_bytes(), chunk_size, false).unwrap(),
+                    expected_part1,
+                    "part1 with chunk size {chunk_size}"
+                );
+                assert_eq!(
+                    evaluate_stream(input.as_bytes(), chunk_size, true).unwrap(),
+                    expected_part2,
+                    "part2 with chunk size {chunk_size}"
+                );
+            }
+        }
+    }
+
+    // the products of the instructions that are enabled, added up in a u128 that cannot overflow
+    fn reference_sum(input: &str, use_do_dont_markers: bool) -> u128 {
+        let instruction_regex =
+            Regex::new(&format!(r"{MUL_PATTERN}|(?<do>do\(\))|don't\(\)")).unwrap();
+        let mut is_enabled = true;
+        let mut sum: u128 = 0;
+
+        for captures in instruction_regex.captures_iter(input) {
+            match (captures.name("x"), captures.name("y")) {
+                (Some(x), Some(y)) if is_enabled || !use_do_dont_markers => {
+                    sum +=
+                        x.as_str().parse::<u128>().unwrap() * y.as_str().parse::<u128>().unwrap();
+                }
+                (Some(_), Some(_)) => {}
+                _ => is_enabled = captures.name("do").is_some(),
+            }
+        }
+
+        sum
+    }
+
+    #[test]
+    fn test_evaluate_stream_past_u32() {
+        let input = generate_input(7, 2 << 20);
+
+        for use_do_dont_markers in [false, true] {
+            let expected = reference_sum(&input, use_do_dont_markers);
+            assert!(expected > u32::MAX as u128);
+
+            assert_eq!(
+                evaluate_stream(input.as_bytes(), 4096, use_do_dont_markers).unwrap(),
+                Answer::from(expected)
+            );
+        }
+    }
 }
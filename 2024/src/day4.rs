@@ -1,3 +1,5 @@
+use std::collections::VecDeque;
+
 use aoc_runner_derive::aoc;
 
 fn parse(input: &str) -> Vec<Vec<char>> {
@@ -7,367 +9,720 @@ fn parse(input: &str) -> Vec<Vec<char>> {
         .collect()
 }
 
-const XMAS_FORWARDS: [char; 4] = ['X', 'M', 'A', 'S'];
-const XMAS_BACKWARDS: [char; 4] = ['S', 'A', 'M', 'X'];
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Direction {
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+    Up,
+    UpRight,
+}
 
-struct WordSearch {
-    word_search: Vec<Vec<char>>,
-    cur_item: Option<WordSearchItem>,
-    save_point: Option<WordSearchItem>,
-    num_rows: usize,
-    num_cols: usize,
+impl Direction {
+    const ALL: [Direction; 8] = [
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+        Direction::Up,
+        Direction::UpRight,
+    ];
+
+    fn row_col_offset(&self) -> (isize, isize) {
+        match self {
+            Direction::Right => (0, 1),
+            Direction::DownRight => (1, 1),
+            Direction::Down => (1, 0),
+            Direction::DownLeft => (1, -1),
+            Direction::Left => (0, -1),
+            Direction::UpLeft => (-1, -1),
+            Direction::Up => (-1, 0),
+            Direction::UpRight => (-1, 1),
+        }
+    }
 }
 
-struct WordSearchItem {
+#[derive(Debug, PartialEq, Eq, Hash)]
+pub struct WordMatch {
     row_i: usize,
     col_i: usize,
-    character: char,
+    direction: Direction,
+    word: String,
 }
 
-impl Clone for WordSearchItem {
-    fn clone(&self) -> WordSearchItem {
-        WordSearchItem {
-            row_i: self.row_i,
-            col_i: self.col_i,
-            character: self.character,
-        }
+impl WordMatch {
+    pub fn word(&self) -> &str {
+        &self.word
     }
-}
 
-impl Iterator for WordSearch {
-    type Item = WordSearchItem;
-
-    fn next(&mut self) -> Option<WordSearchItem> {
-        if self.cur_item.is_none() {
-            panic!("cannot retrieve next item; cur_item is none!");
-        }
-
-        let cur_item = self.cur_item.as_ref().unwrap();
-
-        let mut next_col_i = cur_item.col_i + 1;
-        let mut next_row_i = cur_item.row_i;
+    /// The (row, column) of every letter of the word, in reading order
+    pub fn cells(&self) -> Vec<(usize, usize)> {
+        let (row_offset, col_offset) = self.direction.row_col_offset();
+
+        (0..self.word.chars().count() as isize)
+            .map(|i| {
+                (
+                    self.row_i.wrapping_add_signed(i * row_offset),
+                    self.col_i.wrapping_add_signed(i * col_offset),
+                )
+            })
+            .collect()
+    }
+}
 
-        if self.num_cols <= cur_item.col_i + 1 {
-            next_col_i = 0;
-            next_row_i = next_row_i + 1;
-        }
+struct TrieNode {
+    children: Vec<(char, usize)>,
+    word_i: Option<usize>,
+    // the node of the longest proper suffix of this node's prefix that is also in the trie
+    suffix_i: usize,
+    // the node of the longest proper suffix that is a whole word, if any
+    word_suffix_i: Option<usize>,
+}
 
-        if self.num_rows <= next_row_i {
-            return self.set_cur_item_none();
+impl TrieNode {
+    fn new() -> TrieNode {
+        TrieNode {
+            children: Vec::new(),
+            word_i: None,
+            suffix_i: 0,
+            word_suffix_i: None,
         }
-
-        self.set_cur_item(next_row_i, next_col_i)
     }
 }
 
-trait WordSearchIterator: Iterator {
-    fn save_point(&mut self) -> Option<WordSearchItem>;
+// every word is stored in one trie with Aho-Corasick suffix links, so a single pass along every line of the grid in
+// each direction finds all of the words, in time linear in the cells plus the matches however many words there are
+struct WordSearchEngine {
+    trie: Vec<TrieNode>,
+    words: Vec<String>,
+}
 
-    fn reset_save_point(&mut self);
+impl WordSearchEngine {
+    fn new(words: &[&str]) -> WordSearchEngine {
+        let mut engine = WordSearchEngine {
+            trie: vec![TrieNode::new()],
+            words: Vec::new(),
+        };
 
-    fn reset_save_point_to_item(&mut self, word_search_item: &Option<WordSearchItem>);
+        for word in words {
+            engine.add(word);
+        }
 
-    fn right(&mut self) -> Option<WordSearchItem>;
+        engine.link_suffixes();
 
-    fn down(&mut self) -> Option<WordSearchItem>;
+        engine
+    }
 
-    fn down_right(&mut self) -> Option<WordSearchItem>;
+    fn add(&mut self, word: &str) {
+        if word.is_empty() {
+            return;
+        }
 
-    fn down_left(&mut self) -> Option<WordSearchItem>;
-}
+        let mut node_i = 0;
+
+        for c in word.chars() {
+            node_i = match self.child(node_i, c) {
+                Some(child_i) => child_i,
+                None => {
+                    self.trie.push(TrieNode::new());
+                    let child_i = self.trie.len() - 1;
+                    self.trie[node_i].children.push((c, child_i));
+                    child_i
+                }
+            };
+        }
 
-impl WordSearchIterator for WordSearch {
-    fn save_point(&mut self) -> Option<WordSearchItem> {
-        self.save_point = self.cur_item.clone();
-        return self.save_point.clone();
+        if self.trie[node_i].word_i.is_none() {
+            self.words.push(word.to_string());
+            self.trie[node_i].word_i = Some(self.words.len() - 1);
+        }
     }
 
-    fn reset_save_point(&mut self) {
-        self.reset_save_point_to_item(&self.save_point.clone());
+    fn child(&self, node_i: usize, c: char) -> Option<usize> {
+        self.trie[node_i]
+            .children
+            .iter()
+            .find(|(child_c, _)| *child_c == c)
+            .map(|(_, child_i)| *child_i)
     }
 
-    fn reset_save_point_to_item(&mut self, word_search_item: &Option<WordSearchItem>) {
-        self.cur_item = word_search_item.clone()
-    }
+    // breadth first, so the suffix links of shorter prefixes are known before they are followed
+    fn link_suffixes(&mut self) {
+        let mut queue: VecDeque<usize> = self.trie[0]
+            .children
+            .iter()
+            .map(|(_, child_i)| *child_i)
+            .collect();
 
-    fn right(&mut self) -> Option<WordSearchItem> {
-        if self.cur_item.is_none() {
-            return self.cur_item.clone();
-        }
+        while let Some(node_i) = queue.pop_front() {
+            for (c, child_i) in self.trie[node_i].children.clone() {
+                let suffix_i = self.next(self.trie[node_i].suffix_i, c);
+                let suffix = &self.trie[suffix_i];
+                let word_suffix_i = suffix.word_i.map(|_| suffix_i).or(suffix.word_suffix_i);
 
-        let cur_item = self.cur_item.as_ref().unwrap();
+                self.trie[child_i].suffix_i = suffix_i;
+                self.trie[child_i].word_suffix_i = word_suffix_i;
 
-        if self.num_cols <= cur_item.col_i + 1 {
-            return self.set_cur_item_none();
+                queue.push_back(child_i);
+            }
         }
-
-        self.set_cur_item(cur_item.row_i, cur_item.col_i + 1)
     }
 
-    fn down(&mut self) -> Option<WordSearchItem> {
-        if self.cur_item.is_none() {
-            return self.cur_item.clone();
-        }
+    // the node of the longest suffix of node_i's prefix followed by c
+    fn next(&self, mut node_i: usize, c: char) -> usize {
+        loop {
+            if let Some(child_i) = self.child(node_i, c) {
+                return child_i;
+            }
 
-        let cur_item = self.cur_item.as_ref().unwrap();
+            if node_i == 0 {
+                return 0;
+            }
 
-        if self.num_rows <= cur_item.row_i + 1 {
-            return self.set_cur_item_none();
+            node_i = self.trie[node_i].suffix_i;
         }
-
-        self.set_cur_item(cur_item.row_i + 1, cur_item.col_i)
     }
 
-    fn down_right(&mut self) -> Option<WordSearchItem> {
-        if self.cur_item.is_none() {
-            return self.cur_item.clone();
-        }
-
-        let cur_item = self.cur_item.as_ref().unwrap();
+    fn find_all(&self, grid: &[Vec<char>]) -> Vec<WordMatch> {
+        let mut word_matches = Vec::new();
 
-        if self.num_cols <= cur_item.col_i + 1 || self.num_rows <= cur_item.row_i + 1 {
-            return self.set_cur_item_none();
+        for direction in Direction::ALL {
+            for line in lines(grid, direction) {
+                self.find_in_line(grid, &line, direction, &mut word_matches);
+            }
         }
 
-        self.set_cur_item(cur_item.row_i + 1, cur_item.col_i + 1)
+        word_matches
     }
 
-    fn down_left(&mut self) -> Option<WordSearchItem> {
-        if self.cur_item.is_none() {
-            return self.cur_item.clone();
-        }
-
-        let cur_item = self.cur_item.as_ref().unwrap();
-
-        if cur_item.col_i == 0 || self.num_rows <= cur_item.row_i + 1 {
-            return self.set_cur_item_none();
+    fn find_in_line(
+        &self,
+        grid: &[Vec<char>],
+        line: &[(usize, usize)],
+        direction: Direction,
+        word_matches: &mut Vec<WordMatch>,
+    ) {
+        let mut node_i = 0;
+
+        for (end_i, (row_i, col_i)) in line.iter().enumerate() {
+            node_i = self.next(node_i, grid[*row_i][*col_i]);
+
+            let node = &self.trie[node_i];
+            let mut word_node_i = node.word_i.map(|_| node_i).or(node.word_suffix_i);
+
+            while let Some(i) = word_node_i {
+                let word = &self.words[self.trie[i].word_i.unwrap()];
+                let length = word.chars().count();
+
+                // a single letter reads the same in every direction, so only report it once
+                if length > 1 || direction == Direction::ALL[0] {
+                    let (row_i, col_i) = line[end_i + 1 - length];
+
+                    word_matches.push(WordMatch {
+                        row_i,
+                        col_i,
+                        direction,
+                        word: word.clone(),
+                    });
+                }
+
+                word_node_i = self.trie[i].word_suffix_i;
+            }
         }
-
-        self.set_cur_item(cur_item.row_i + 1, cur_item.col_i - 1)
     }
 }
 
-impl WordSearch {
-    fn new(input: &str) -> WordSearch {
-        let word_search = parse(input);
-        let row_one = word_search.get(0).unwrap();
-
-        WordSearch {
-            word_search: word_search.clone(),
-            cur_item: Some(WordSearchItem {
-                row_i: 0,
-                col_i: 0,
-                character: *word_search.get(0).unwrap().get(0).unwrap(),
-            }),
-            save_point: None,
-            num_rows: word_search.len(),
-            num_cols: row_one.len(),
-        }
-    }
+/// The cells of every line of the grid in `direction`, each in reading order; every cell is on exactly one of them
+fn lines(grid: &[Vec<char>], direction: Direction) -> Vec<Vec<(usize, usize)>> {
+    let (row_offset, col_offset) = direction.row_col_offset();
 
-    fn set_cur_item(&mut self, next_row_i: usize, next_col_i: usize) -> Option<WordSearchItem> {
-        self.cur_item = Some(WordSearchItem {
-            row_i: next_row_i,
-            col_i: next_col_i,
-            character: self.get_char(next_row_i, next_col_i),
-        });
+    let step = |(row_i, col_i): (usize, usize), sign: isize| {
+        row_i
+            .checked_add_signed(sign * row_offset)
+            .zip(col_i.checked_add_signed(sign * col_offset))
+            .filter(|(row_i, col_i)| grid.get(*row_i).is_some_and(|row| *col_i < row.len()))
+    };
 
-        self.cur_item.clone()
-    }
+    let mut lines = Vec::new();
 
-    fn set_cur_item_none(&mut self) -> Option<WordSearchItem> {
-        self.cur_item = None;
+    for (row_i, row) in grid.iter().enumerate() {
+        for col_i in 0..row.len() {
+            // a line starts at every cell without one before it
+            if step((row_i, col_i), -1).is_some() {
+                continue;
+            }
 
-        return None;
-    }
+            let mut line = vec![(row_i, col_i)];
 
-    fn get_char(&self, row_i: usize, col_i: usize) -> char {
-        return *self.word_search.get(row_i).unwrap().get(col_i).unwrap();
+            while let Some(cell) = step(*line.last().unwrap(), 1) {
+                line.push(cell);
+            }
+
+            lines.push(line);
+        }
     }
+
+    lines
 }
 
-fn found_rest_of_word(
-    word_search: &mut WordSearch,
-    rest_word: Vec<char>,
-    get_next_item: fn(word_search: &mut WordSearch) -> Option<WordSearchItem>,
-) -> bool {
-    for c in rest_word {
-        let item = get_next_item(word_search);
+pub fn find_words(input: &str, words: &[&str]) -> Vec<WordMatch> {
+    WordSearchEngine::new(words).find_all(&parse(input))
+}
 
-        match item {
-            Some(value) if value.character == c => {
-                continue;
-            }
-            _ => {
-                return false;
-            }
-        };
-    }
+#[aoc(day4, part1)]
+fn part1(input: &str) -> u32 {
+    let word_search_engine = WordSearchEngine::new(&["XMAS"]);
+
+    word_search_engine.find_all(&parse(input)).len() as u32
+}
+
+const STENCIL_WILDCARD: char = '.';
 
-    return true;
+#[derive(Clone, Debug, PartialEq)]
+struct Stencil {
+    cells: Vec<Vec<Option<char>>>,
 }
 
-fn get_count_in_all_directions(
-    word_search: &mut WordSearch,
-    xmas_word: [char; 4],
-    word_search_item: &WordSearchItem,
-) -> u8 {
-    let mut count: u8 = 0;
+impl Stencil {
+    // rows are separated by `/` and `.` matches any character, e.g. `M.S/.A./M.S`; a pattern without any letter would
+    // match every cell, so it is rejected
+    fn parse(pattern: &str) -> Result<Stencil, String> {
+        if pattern.chars().all(|c| c == STENCIL_WILDCARD || c == '/') {
+            return Err(format!("stencil {pattern:?} has no character to match"));
+        }
 
-    let [first_character, rest_word @ ..] = xmas_word;
+        let mut cells: Vec<Vec<Option<char>>> = pattern
+            .split('/')
+            .map(|row| {
+                row.chars()
+                    .map(|c| (c != STENCIL_WILDCARD).then_some(c))
+                    .collect()
+            })
+            .collect();
 
-    if word_search_item.character == first_character {
-        word_search.save_point();
+        let num_cols = cells.iter().map(|row| row.len()).max().unwrap_or(0);
 
-        if found_rest_of_word(word_search, rest_word.to_vec(), |word_search| {
-            word_search.right()
-        }) {
-            count = count + 1;
+        for row in cells.iter_mut() {
+            row.resize(num_cols, None);
         }
 
-        word_search.reset_save_point();
+        // rows and columns of only wildcards at the edges constrain nothing, so they go; otherwise they would keep the
+        // stencil from matching at the grid's edges, where the anchor would be outside the grid
+        let is_wildcard_row = |row: &Vec<Option<char>>| row.iter().all(Option::is_none);
 
-        if found_rest_of_word(word_search, rest_word.to_vec(), |word_search| {
-            word_search.down()
-        }) {
-            count = count + 1;
+        while cells.last().is_some_and(is_wildcard_row) {
+            cells.pop();
+        }
+        while cells.first().is_some_and(is_wildcard_row) {
+            cells.remove(0);
+        }
+        while cells.iter().all(|row| row.last() == Some(&None)) {
+            cells.iter_mut().for_each(|row| {
+                row.pop();
+            });
+        }
+        while cells.iter().all(|row| row.first() == Some(&None)) {
+            cells.iter_mut().for_each(|row| {
+                row.remove(0);
+            });
         }
 
-        word_search.reset_save_point();
+        Ok(Stencil { cells })
+    }
+
+    fn num_rows(&self) -> usize {
+        self.cells.len()
+    }
 
-        if found_rest_of_word(word_search, rest_word.to_vec(), |word_search| {
-            word_search.down_right()
-        }) {
-            count = count + 1;
+    fn num_cols(&self) -> usize {
+        self.cells.first().map_or(0, |row| row.len())
+    }
+
+    fn rotate_clockwise(&self) -> Stencil {
+        Stencil {
+            cells: (0..self.num_cols())
+                .map(|col_i| {
+                    (0..self.num_rows())
+                        .rev()
+                        .map(|row_i| self.cells[row_i][col_i])
+                        .collect()
+                })
+                .collect(),
         }
+    }
+
+    fn reflect(&self) -> Stencil {
+        Stencil {
+            cells: self
+                .cells
+                .iter()
+                .map(|row| row.iter().rev().copied().collect())
+                .collect(),
+        }
+    }
+
+    // the distinct stencils among the 4 rotations of this stencil and of its reflection
+    fn rotations_and_reflections(&self) -> Vec<Stencil> {
+        let mut stencils: Vec<Stencil> = Vec::new();
 
-        word_search.reset_save_point();
+        for mut stencil in [self.clone(), self.reflect()] {
+            for _ in 0..4 {
+                if !stencils.contains(&stencil) {
+                    stencils.push(stencil.clone());
+                }
 
-        if found_rest_of_word(word_search, rest_word.to_vec(), |word_search| {
-            word_search.down_left()
-        }) {
-            count = count + 1;
+                stencil = stencil.rotate_clockwise();
+            }
         }
 
-        word_search.reset_save_point();
+        stencils
     }
 
-    return count;
+    fn matches_at(&self, grid: &[Vec<char>], row_i: usize, col_i: usize) -> bool {
+        self.cells.iter().enumerate().all(|(stencil_row_i, row)| {
+            row.iter().enumerate().all(|(stencil_col_i, cell)| {
+                let grid_c = grid
+                    .get(row_i + stencil_row_i)
+                    .and_then(|grid_row| grid_row.get(col_i + stencil_col_i));
+
+                // a wildcard matches anything, even beyond the grid
+                cell.is_none_or(|c| grid_c == Some(&c))
+            })
+        })
+    }
 }
 
-#[aoc(day4, part1)]
-fn part1(input: &str) -> u32 {
-    let mut word_search = WordSearch::new(input);
+#[derive(Debug, PartialEq)]
+struct StencilMatch {
+    row_i: usize,
+    col_i: usize,
+    stencil_i: usize,
+}
 
-    let mut count: u32 = 0;
+struct StencilMatcher {
+    stencils: Vec<Stencil>,
+}
 
-    let mut word_search_item = word_search.next();
+impl StencilMatcher {
+    fn new(
+        pattern: &str,
+        include_rotations_and_reflections: bool,
+    ) -> Result<StencilMatcher, String> {
+        let stencil = Stencil::parse(pattern)?;
+
+        Ok(StencilMatcher {
+            stencils: if include_rotations_and_reflections {
+                stencil.rotations_and_reflections()
+            } else {
+                vec![stencil]
+            },
+        })
+    }
 
-    while word_search_item.is_some() {
-        count = count
-            + get_count_in_all_directions(
-                &mut word_search,
-                XMAS_FORWARDS,
-                word_search_item.as_ref().unwrap(),
-            ) as u32;
-        count = count
-            + get_count_in_all_directions(
-                &mut word_search,
-                XMAS_BACKWARDS,
-                word_search_item.as_ref().unwrap(),
-            ) as u32;
+    // row_i and col_i are the top left corner of the matched stencil
+    fn find_all(&self, grid: &[Vec<char>]) -> Vec<StencilMatch> {
+        let mut stencil_matches = Vec::new();
+
+        for (row_i, row) in grid.iter().enumerate() {
+            for col_i in 0..row.len() {
+                for (stencil_i, stencil) in self.stencils.iter().enumerate() {
+                    if stencil.matches_at(grid, row_i, col_i) {
+                        stencil_matches.push(StencilMatch {
+                            row_i,
+                            col_i,
+                            stencil_i,
+                        });
+                    }
+                }
+            }
+        }
 
-        word_search_item = word_search.next();
+        stencil_matches
     }
 
-    return count;
+    fn count(&self, grid: &[Vec<char>]) -> usize {
+        self.find_all(grid).len()
+    }
 }
 
-const MAS: [char; 3] = ['M', 'A', 'S'];
-const SAM: [char; 3] = ['S', 'A', 'M'];
-
-fn is_mas_shaped_x(word_search: &mut WordSearch, word_search_item: &WordSearchItem) -> bool {
-    let [mas_first_character, mas_rest_word @ ..] = MAS;
-    let [sam_first_character, sam_rest_word @ ..] = SAM;
+#[aoc(day4, part2)]
+fn part2(input: &str) -> u32 {
+    let x_mas_matcher = StencilMatcher::new("M.S/.A./M.S", true).unwrap();
 
-    word_search.save_point();
+    x_mas_matcher.count(&parse(input)) as u32
+}
 
-    let mut found_down_right = false;
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use super::*;
+
+    const EXAMPLE: &str = "MMMSXXMASM
+MSAMXMSMSA
+AMXSXMAAMM
+MSAMASMSMX
+XMASAMXAMM
+XXAMMXXAMA
+SMSMSASXSS
+SAXAMASAAA
+MAMMMXMMMM
+MXMXAXMASX";
+
+    // checks every cell, direction and word separately
+    fn find_all_brute_force(words: &[&str], grid: &[Vec<char>]) -> HashSet<WordMatch> {
+        let mut word_matches = HashSet::new();
+
+        for row_i in 0..grid.len() {
+            for col_i in 0..grid[row_i].len() {
+                for direction in Direction::ALL {
+                    let (row_offset, col_offset) = direction.row_col_offset();
+
+                    for word in words {
+                        let is_match = word.chars().enumerate().all(|(i, c)| {
+                            let r = row_i as isize + row_offset * i as isize;
+                            let col = col_i as isize + col_offset * i as isize;
+
+                            r >= 0
+                                && col >= 0
+                                && grid
+                                    .get(r as usize)
+                                    .and_then(|row| row.get(col as usize))
+                                    .is_some_and(|grid_c| *grid_c == c)
+                        });
+
+                        if is_match && (word.len() > 1 || direction == Direction::Right) {
+                            word_matches.insert(WordMatch {
+                                row_i,
+                                col_i,
+                                direction,
+                                word: word.to_string(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
 
-    if mas_first_character == word_search_item.character
-        && found_rest_of_word(word_search, mas_rest_word.to_vec(), |word_search| {
-            word_search.down_right()
-        })
-    {
-        found_down_right = true;
+        word_matches
     }
 
-    word_search.reset_save_point();
+    #[test]
+    fn test_part1_example() {
+        assert_eq!(part1(EXAMPLE), 18);
+    }
 
-    if !found_down_right
-        && sam_first_character == word_search_item.character
-        && found_rest_of_word(word_search, sam_rest_word.to_vec(), |word_search| {
-            word_search.down_right()
+    #[test]
+    fn test_find_all_returns_start_and_direction() {
+        let grid = parse("XMAS\nAXAB\nSMXA\nXSAX");
+        let word_search_engine = WordSearchEngine::new(&["XMAS", "AXA", "BAX"]);
+
+        let word_matches: HashSet<WordMatch> =
+            word_search_engine.find_all(&grid).into_iter().collect();
+
+        let expected = [
+            (0, 0, Direction::Right, "XMAS"),
+            (3, 0, Direction::UpRight, "XMAS"),
+            (1, 0, Direction::Right, "AXA"),
+            (1, 2, Direction::Left, "AXA"),
+            (1, 2, Direction::Down, "AXA"),
+            (3, 2, Direction::Up, "AXA"),
+            (1, 3, Direction::Left, "BAX"),
+            (1, 3, Direction::Down, "BAX"),
+        ]
+        .into_iter()
+        .map(|(row_i, col_i, direction, word)| WordMatch {
+            row_i,
+            col_i,
+            direction,
+            word: word.to_string(),
         })
-    {
-        found_down_right = true;
+        .collect::<HashSet<WordMatch>>();
+
+        assert_eq!(word_matches, expected);
     }
 
-    word_search.reset_save_point();
+    #[test]
+    fn test_find_all_matches_brute_force() {
+        let grid = parse(EXAMPLE);
+        let words = ["XMAS", "SAMX", "MAS", "MA", "M", "XMASAMX", "AXA", "MMM"];
 
-    let mut found_down_left = false;
+        let word_matches = WordSearchEngine::new(&words).find_all(&grid);
+        let num_word_matches = word_matches.len();
+        let word_matches: HashSet<WordMatch> = word_matches.into_iter().collect();
 
-    if found_down_right {
-        word_search.right();
-        let down_left_item_start_option = word_search.right();
+        assert_eq!(num_word_matches, word_matches.len());
+        assert_eq!(word_matches, find_all_brute_force(&words, &grid));
+    }
 
-        if down_left_item_start_option.is_some() {
-            let down_left_item_start = down_left_item_start_option.clone().unwrap();
+    #[test]
+    fn test_find_all_matches_brute_force_on_a_large_grid() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let letters = ['X', 'M', 'A', 'S'];
+
+        let grid: Vec<Vec<char>> = (0..80)
+            .map(|_| (0..80).map(|_| letters[rng.random_range(0..4)]).collect())
+            .collect();
+        let words: Vec<String> = (0..60)
+            .map(|_| {
+                (0..rng.random_range(1..=7))
+                    .map(|_| letters[rng.random_range(0..4)])
+                    .collect()
+            })
+            .collect();
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+
+        let word_matches: HashSet<WordMatch> = WordSearchEngine::new(&words)
+            .find_all(&grid)
+            .into_iter()
+            .collect();
+
+        assert_eq!(word_matches, find_all_brute_force(&words, &grid));
+    }
 
-            if mas_first_character == down_left_item_start.character
-                && found_rest_of_word(word_search, mas_rest_word.to_vec(), |word_search| {
-                    word_search.down_left()
-                })
-            {
-                found_down_left = true;
-            }
+    // what keeps the search linear: the automaton reads every cell once per direction, not once per word or per
+    // starting cell
+    #[test]
+    fn test_lines_cover_every_cell_once() {
+        let grid = parse("ABCD\nEFG\nHIJKL\n\nMN");
+        let num_cells: usize = grid.iter().map(Vec::len).sum();
+
+        for direction in Direction::ALL {
+            let cells: Vec<(usize, usize)> =
+                lines(&grid, direction).into_iter().flatten().collect();
+
+            assert_eq!(cells.len(), num_cells, "{direction:?}");
+            assert_eq!(
+                cells.iter().collect::<HashSet<_>>().len(),
+                num_cells,
+                "{direction:?}"
+            );
+        }
 
-            word_search.reset_save_point_to_item(&down_left_item_start_option);
+        assert_eq!(
+            lines(&grid, Direction::DownRight)[..2],
+            [vec![(0, 0), (1, 1), (2, 2)], vec![(0, 1), (1, 2), (2, 3)]]
+        );
+    }
 
-            if !found_down_left
-                && sam_first_character == down_left_item_start.character
-                && found_rest_of_word(word_search, sam_rest_word.to_vec(), |word_search| {
-                    word_search.down_left()
-                })
-            {
-                found_down_left = true;
-            }
-        }
+    #[test]
+    fn test_word_match_cells() {
+        let word_matches = find_words("XMAS\nAXAB\nSMXA\nXSAX", &["XMAS"]);
 
-        word_search.reset_save_point();
+        let cells: HashSet<Vec<(usize, usize)>> =
+            word_matches.iter().map(WordMatch::cells).collect();
+
+        assert_eq!(
+            cells,
+            HashSet::from([
+                vec![(0, 0), (0, 1), (0, 2), (0, 3)],
+                vec![(3, 0), (2, 1), (1, 2), (0, 3)],
+            ])
+        );
     }
 
-    return found_down_right && found_down_left;
-}
+    #[test]
+    fn test_part2_example() {
+        assert_eq!(part2(EXAMPLE), 9);
+    }
 
-#[aoc(day4, part2)]
-fn part2(input: &str) -> u32 {
-    let mut word_search = WordSearch::new(input);
+    #[test]
+    fn test_stencil_rotations_and_reflections() {
+        let stencil = Stencil::parse("AB./..C").unwrap();
+
+        assert_eq!(
+            stencil.rotate_clockwise(),
+            Stencil::parse(".A/.B/C.").unwrap()
+        );
+        assert_eq!(stencil.reflect(), Stencil::parse(".BA/C..").unwrap());
+        assert_eq!(stencil.rotations_and_reflections().len(), 8);
+
+        let x_mas_stencils = Stencil::parse("M.S/.A./M.S")
+            .unwrap()
+            .rotations_and_reflections();
+
+        assert_eq!(
+            x_mas_stencils,
+            ["M.S/.A./M.S", "M.M/.A./S.S", "S.M/.A./S.M", "S.S/.A./M.M"]
+                .map(|pattern| Stencil::parse(pattern).unwrap())
+                .to_vec()
+        );
+    }
 
-    let mut count: u32 = 0;
+    #[test]
+    fn test_stencil_find_all() {
+        let grid = parse("ABAB\nBABA\nABAB");
+        let stencil_matcher = StencilMatcher::new("A./.A", false).unwrap();
+
+        assert_eq!(
+            stencil_matcher.find_all(&grid),
+            vec![
+                StencilMatch {
+                    row_i: 0,
+                    col_i: 0,
+                    stencil_i: 0
+                },
+                StencilMatch {
+                    row_i: 0,
+                    col_i: 2,
+                    stencil_i: 0
+                },
+                StencilMatch {
+                    row_i: 1,
+                    col_i: 1,
+                    stencil_i: 0
+                },
+            ]
+        );
+        assert_eq!(
+            StencilMatcher::new("A./.A", true).unwrap().count(&grid),
+            3 + 3
+        );
+        assert_eq!(
+            StencilMatcher::new("AB.", true).unwrap().count(&grid),
+            5 + 4 + 4 + 4
+        );
+    }
 
-    let mut word_search_item = word_search.next();
+    #[test]
+    fn test_stencil_wildcards_at_the_grid_edge() {
+        let grid = parse("ABAB\nBABA\nABAB");
+
+        assert_eq!(
+            Stencil::parse("A./.A/../").unwrap(),
+            Stencil::parse("A./.A").unwrap()
+        );
+        assert_eq!(
+            Stencil::parse("./.A.").unwrap(),
+            Stencil::parse("A").unwrap()
+        );
+
+        // the bottom right A matches too, although the trailing wildcards reach past the grid
+        assert_eq!(
+            StencilMatcher::new("A../.A./...", false)
+                .unwrap()
+                .count(&grid),
+            3
+        );
+        assert_eq!(StencilMatcher::new("B./.", true).unwrap().count(&grid), 6);
+    }
 
-    while word_search_item.is_some() {
-        if is_mas_shaped_x(&mut word_search, word_search_item.as_ref().unwrap()) {
-            count = count + 1;
+    #[test]
+    fn test_stencil_without_characters() {
+        for pattern in ["", ".", "../..", "/"] {
+            assert_eq!(
+                Stencil::parse(pattern),
+                Err(format!("stencil {pattern:?} has no character to match"))
+            );
         }
 
-        word_search_item = word_search.next();
+        assert!(StencilMatcher::new("./.", true).is_err());
     }
-
-    return count;
 }
-
-#[cfg(test)]
-mod tests {}
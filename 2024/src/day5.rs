@@ -1,17 +1,28 @@
 use std::{
-    cmp::Ordering,
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    error::Error,
+    fmt,
 };
 
 use aoc_runner_derive::aoc;
+use aoc_tools::{
+    answer::{Answer, Overflow, SolutionError, checked_add, checked_sum},
+    cached,
+    input::{InputError, parse_number},
+    repl::{Explore, QueryResult},
+};
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom};
+use serde::{Deserialize, Serialize};
 
-type PageNumber = u16;
+pub type PageNumber = u16;
 
+#[derive(Clone, Copy, Debug, PartialEq)]
 struct PageOrderingRule {
     page_before: PageNumber,
     page_after: PageNumber,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
 struct PageOrderingRules {
     page_numbers_before_map: HashMap<PageNumber, HashSet<PageNumber>>,
     page_numbers_after_map: HashMap<PageNumber, HashSet<PageNumber>>,
@@ -90,39 +101,407 @@ impl PageOrderingRules {
     }
 }
 
-fn parse(input: &str) -> (Vec<PageOrderingRule>, Vec<Vec<PageNumber>>) {
-    let split_vec: Vec<&str> = input.split("\n\n").collect();
+#[derive(Debug, PartialEq)]
+pub enum PageOrderingError {
+    Cycle(Vec<PageNumber>),
+    Ambiguous(PageNumber, PageNumber),
+    Input(InputError),
+    Overflow(Overflow),
+}
 
-    let page_ordering_rules = split_vec.get(0).unwrap();
+impl From<InputError> for PageOrderingError {
+    fn from(e: InputError) -> Self {
+        PageOrderingError::Input(e)
+    }
+}
 
-    let page_ordering_rules: Vec<PageOrderingRule> = page_ordering_rules
-        .lines()
-        .map(|l| {
-            let parsed_page_numbers: Vec<PageNumber> = l
-                .split("|")
-                .map(|n| n.parse::<PageNumber>().unwrap())
+impl From<Overflow> for PageOrderingError {
+    fn from(overflow: Overflow) -> Self {
+        PageOrderingError::Overflow(overflow)
+    }
+}
+
+impl fmt::Display for PageOrderingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PageOrderingError::Cycle(page_numbers) => {
+                let page_numbers: Vec<String> =
+                    page_numbers.iter().map(|p| p.to_string()).collect();
+                write!(
+                    f,
+                    "page ordering rules form a cycle: {}",
+                    page_numbers.join(" -> ")
+                )
+            }
+            PageOrderingError::Ambiguous(page_number_1, page_number_2) => write!(
+                f,
+                "no page ordering rule orders pages {page_number_1} and {page_number_2}"
+            ),
+            PageOrderingError::Input(e) => write!(f, "{e}"),
+            PageOrderingError::Overflow(overflow) => write!(f, "{overflow}"),
+        }
+    }
+}
+
+impl Error for PageOrderingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PageOrderingError::Overflow(overflow) => Some(overflow),
+            _ => None,
+        }
+    }
+}
+
+impl PageOrderingRules {
+    // Kahn's algorithm over only the rules between `page_numbers`; there must be exactly one page without
+    // unplaced pages before it at every step, otherwise the order is ambiguous or the rules have a cycle
+    fn topological_order(
+        &self,
+        page_numbers: &[PageNumber],
+    ) -> Result<Vec<PageNumber>, PageOrderingError> {
+        let mut num_page_numbers_before: HashMap<PageNumber, usize> = page_numbers
+            .iter()
+            .map(|page_number| {
+                let num_before = page_numbers
+                    .iter()
+                    .filter(|page_number_before| {
+                        self.is_page_number_before(**page_number_before, *page_number)
+                    })
+                    .count();
+
+                (*page_number, num_before)
+            })
+            .collect();
+
+        let mut ordered_page_numbers: Vec<PageNumber> = Vec::new();
+
+        while !num_page_numbers_before.is_empty() {
+            let next_page_numbers: Vec<PageNumber> = page_numbers
+                .iter()
+                .copied()
+                .filter(|page_number| num_page_numbers_before.get(page_number) == Some(&0))
                 .collect();
-            let page_before = parsed_page_numbers.get(0).unwrap();
-            let page_after = parsed_page_numbers.get(1).unwrap();
-            PageOrderingRule {
-                page_before: *page_before,
-                page_after: *page_after,
+
+            let next_page_number = match next_page_numbers.as_slice() {
+                [next_page_number] => *next_page_number,
+                [page_number_1, page_number_2, ..] => {
+                    return Err(PageOrderingError::Ambiguous(*page_number_1, *page_number_2));
+                }
+                [] => {
+                    let remaining_page_numbers: Vec<PageNumber> = page_numbers
+                        .iter()
+                        .copied()
+                        .filter(|page_number| num_page_numbers_before.contains_key(page_number))
+                        .collect();
+
+                    return Err(PageOrderingError::Cycle(
+                        self.find_cycle(&remaining_page_numbers),
+                    ));
+                }
+            };
+
+            num_page_numbers_before.remove(&next_page_number);
+
+            for (page_number, num_before) in num_page_numbers_before.iter_mut() {
+                if self.is_page_number_before(next_page_number, *page_number) {
+                    *num_before -= 1;
+                }
             }
+
+            ordered_page_numbers.push(next_page_number);
+        }
+
+        Ok(ordered_page_numbers)
+    }
+
+    // every page in `page_numbers` has another one of them before it, so walking backwards through the rules
+    // must eventually revisit a page
+    fn find_cycle(&self, page_numbers: &[PageNumber]) -> Vec<PageNumber> {
+        let mut path: Vec<PageNumber> = vec![page_numbers[0]];
+
+        loop {
+            let page_number = *path.last().unwrap();
+            let page_number_before = *page_numbers
+                .iter()
+                .find(|page_number_before| {
+                    self.is_page_number_before(**page_number_before, page_number)
+                })
+                .unwrap();
+
+            if let Some(cycle_start_i) = path.iter().position(|p| *p == page_number_before) {
+                let mut cycle: Vec<PageNumber> = path[cycle_start_i..].to_vec();
+                cycle.push(page_number_before);
+                cycle.reverse();
+
+                return cycle;
+            }
+
+            path.push(page_number_before);
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct PageMove {
+    page_number: PageNumber,
+    from_i: usize,
+    to_i: usize,
+}
+
+#[derive(Debug, PartialEq)]
+struct UpdateRepair {
+    moves: Vec<PageMove>,
+    page_numbers: Vec<PageNumber>,
+}
+
+impl PageOrderingRules {
+    // shortest chain of rules between `page_numbers` showing page_number_before has to come before page_number, if
+    // there is one. like topological_order, it only follows rules between the update's own pages: the full rule set
+    // can have cycles, and a chain through a page the update leaves out does not order anything within it.
+    fn rule_path(
+        &self,
+        page_numbers: &[PageNumber],
+        page_number_before: PageNumber,
+        page_number: PageNumber,
+    ) -> Option<Vec<PageNumber>> {
+        let mut prev_page_numbers: HashMap<PageNumber, PageNumber> = HashMap::new();
+        let mut queue: VecDeque<PageNumber> = VecDeque::from([page_number_before]);
+
+        while let Some(cur_page_number) = queue.pop_front() {
+            for page_number_after in self
+                .page_numbers_after_map
+                .get(&cur_page_number)
+                .into_iter()
+                .flatten()
+            {
+                if *page_number_after == page_number_before
+                    || prev_page_numbers.contains_key(page_number_after)
+                    || !page_numbers.contains(page_number_after)
+                {
+                    continue;
+                }
+
+                prev_page_numbers.insert(*page_number_after, cur_page_number);
+
+                if *page_number_after == page_number {
+                    let mut path = vec![page_number];
+
+                    while let Some(prev_page_number) = prev_page_numbers.get(path.last().unwrap()) {
+                        path.push(*prev_page_number);
+
+                        if *prev_page_number == page_number_before {
+                            break;
+                        }
+                    }
+
+                    path.reverse();
+                    return Some(path);
+                }
+
+                queue.push_back(*page_number_after);
+            }
+        }
+
+        None
+    }
+
+    fn violated_rules(&self, page_numbers: &[PageNumber]) -> Vec<PageOrderingRule> {
+        let mut violated_rules = Vec::new();
+
+        for (i, page_number) in page_numbers.iter().enumerate() {
+            for page_number_before in &page_numbers[i + 1..] {
+                if self.is_page_number_before(*page_number_before, *page_number) {
+                    violated_rules.push(PageOrderingRule {
+                        page_before: *page_number_before,
+                        page_after: *page_number,
+                    });
+                }
+            }
+        }
+
+        violated_rules
+    }
+
+    // pages that are never moved keep their relative order, so the fewest moves leaves in place the largest set of
+    // pages where no later page has to come before an earlier one. "has to come before an earlier page" is a partial
+    // order on the update's indices, and that largest set is its maximum antichain, found with a maximum matching
+    // (Dilworth / König).
+    fn minimal_repair(
+        &self,
+        page_numbers: &[PageNumber],
+    ) -> Result<UpdateRepair, PageOrderingError> {
+        let n = page_numbers.len();
+
+        // must_be_before[i][j]: page i has to come before page j, directly or through other pages of the update
+        let mut must_be_before: Vec<Vec<bool>> = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| self.is_page_number_before(page_numbers[i], page_numbers[j]))
+                    .collect()
+            })
+            .collect();
+
+        for k in 0..n {
+            for i in 0..n {
+                for j in 0..n {
+                    if must_be_before[i][k] && must_be_before[k][j] {
+                        must_be_before[i][j] = true;
+                    }
+                }
+            }
+        }
+
+        if (0..n).any(|i| must_be_before[i][i]) {
+            let cyclic_page_numbers: Vec<PageNumber> = (0..n)
+                .filter(|i| must_be_before[*i][*i])
+                .map(|i| page_numbers[i])
+                .collect();
+
+            return Err(PageOrderingError::Cycle(
+                self.find_cycle(&cyclic_page_numbers),
+            ));
+        }
+
+        let is_conflict = |i: usize, j: usize| i < j && must_be_before[j][i];
+
+        fn augment(
+            i: usize,
+            is_conflict: &dyn Fn(usize, usize) -> bool,
+            is_seen: &mut Vec<bool>,
+            matched_left_i: &mut Vec<Option<usize>>,
+        ) -> bool {
+            for j in 0..matched_left_i.len() {
+                if is_conflict(i, j) && !is_seen[j] {
+                    is_seen[j] = true;
+
+                    if matched_left_i[j]
+                        .is_none_or(|left_i| augment(left_i, is_conflict, is_seen, matched_left_i))
+                    {
+                        matched_left_i[j] = Some(i);
+                        return true;
+                    }
+                }
+            }
+
+            false
+        }
+
+        let mut matched_left_i: Vec<Option<usize>> = vec![None; n];
+
+        for i in 0..n {
+            augment(i, &is_conflict, &mut vec![false; n], &mut matched_left_i);
+        }
+
+        // alternating paths from the unmatched left side give the minimum vertex cover; the indices covered on
+        // neither side form the maximum antichain
+        let is_matched_left: Vec<bool> =
+            (0..n).map(|i| matched_left_i.contains(&Some(i))).collect();
+        let mut is_reachable_left: Vec<bool> = (0..n).map(|i| !is_matched_left[i]).collect();
+        let mut is_reachable_right: Vec<bool> = vec![false; n];
+        let mut queue: VecDeque<usize> = (0..n).filter(|i| is_reachable_left[*i]).collect();
+
+        while let Some(i) = queue.pop_front() {
+            for j in 0..n {
+                if is_conflict(i, j) && !is_reachable_right[j] && matched_left_i[j] != Some(i) {
+                    is_reachable_right[j] = true;
+
+                    if let Some(left_i) = matched_left_i[j]
+                        && !is_reachable_left[left_i]
+                    {
+                        is_reachable_left[left_i] = true;
+                        queue.push_back(left_i);
+                    }
+                }
+            }
+        }
+
+        let is_kept: Vec<bool> = (0..n)
+            .map(|i| is_reachable_left[i] && !is_reachable_right[i])
+            .collect();
+
+        // place pages as early as the rules and the kept pages' order allow, preferring their original order
+        let has_to_be_before =
+            |i: usize, j: usize| must_be_before[i][j] || (is_kept[i] && is_kept[j] && i < j);
+
+        let mut is_placed = vec![false; n];
+        let mut order: Vec<usize> = Vec::new();
+
+        while order.len() < n {
+            let next_i = (0..n)
+                .find(|j| {
+                    !is_placed[*j] && (0..n).all(|i| is_placed[i] || !has_to_be_before(i, *j))
+                })
+                .unwrap();
+
+            is_placed[next_i] = true;
+            order.push(next_i);
+        }
+
+        let moves = order
+            .iter()
+            .enumerate()
+            .filter(|(_, i)| !is_kept[**i])
+            .map(|(to_i, i)| PageMove {
+                page_number: page_numbers[*i],
+                from_i: *i,
+                to_i,
+            })
+            .collect::<Vec<PageMove>>();
+
+        let mut moves = moves;
+        moves.sort_by_key(|page_move| page_move.from_i);
+
+        Ok(UpdateRepair {
+            moves,
+            page_numbers: order.iter().map(|i| page_numbers[*i]).collect(),
         })
-        .collect();
+    }
+}
+
+fn parse(input: &str) -> Result<(Vec<PageOrderingRule>, Vec<Vec<PageNumber>>), InputError> {
+    let Some((page_ordering_rules, page_orderings)) = input.split_once("\n\n") else {
+        return Err(InputError::new(
+            "expected page ordering rules and updates separated by a blank line",
+        ));
+    };
+
+    let page_ordering_rules: Vec<PageOrderingRule> = page_ordering_rules
+        .lines()
+        .enumerate()
+        .map(|(line_i, l)| {
+            let Some((page_before, page_after)) = l.split_once("|") else {
+                return Err(InputError::at_line(line_i, "expected a rule like 47|53"));
+            };
 
-    let page_orderings = split_vec.get(1).unwrap();
+            Ok(PageOrderingRule {
+                page_before: parse_number(page_before, line_i)?,
+                page_after: parse_number(page_after, line_i)?,
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    // updates are numbered from the first line of the input
+    let first_update_line_i = page_ordering_rules.len() + 1;
 
     let page_orderings: Vec<Vec<PageNumber>> = page_orderings
         .lines()
-        .map(|l| {
+        .enumerate()
+        .map(|(line_i, l)| {
             l.split(",")
-                .map(|n| n.parse::<PageNumber>().unwrap())
+                .map(|n| parse_number::<PageNumber>(n, first_update_line_i + line_i))
                 .collect()
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
+
+    return Ok((page_ordering_rules, page_orderings));
+}
+
+// the rule maps and the updates that every part starts from, kept between runs with `--cache`
+#[cached]
+fn parse_rules(input: &str) -> Result<(PageOrderingRules, Vec<Vec<PageNumber>>), InputError> {
+    let (rules, page_orderings) = parse(input)?;
 
-    return (page_ordering_rules, page_orderings);
+    Ok((PageOrderingRules::new_and_populate(&rules), page_orderings))
 }
 
 fn get_middle(numbers: &Vec<PageNumber>) -> PageNumber {
@@ -130,46 +509,41 @@ fn get_middle(numbers: &Vec<PageNumber>) -> PageNumber {
 }
 
 #[aoc(day5, part1)]
-fn part1(input: &str) -> PageNumber {
-    let (rules, page_orderings) = parse(input);
+fn part1(input: &str) -> Result<Answer, SolutionError> {
+    let (page_ordering_rules, page_orderings) = parse_rules(input)?;
 
-    let page_ordering_rules = PageOrderingRules::new_and_populate(&rules);
+    let middles = page_orderings.iter().filter_map(|page_ordering| {
+        let mut page_numbers_before: Vec<PageNumber> = Vec::new();
+        let mut page_numbers_after: Vec<PageNumber> = page_ordering.iter().copied().collect();
 
-    page_orderings
-        .iter()
-        .filter_map(|page_ordering| {
-            let mut page_numbers_before: Vec<PageNumber> = Vec::new();
-            let mut page_numbers_after: Vec<PageNumber> = page_ordering.iter().copied().collect();
+        let mut are_page_numbers_in_proper_order = true;
 
-            let mut are_page_numbers_in_proper_order = true;
+        while !page_numbers_after.is_empty() {
+            let page_number = page_numbers_after.remove(0);
 
-            while !page_numbers_after.is_empty() {
-                let page_number = page_numbers_after.remove(0);
+            if !page_ordering_rules.page_numbers_are_before(&page_numbers_before, page_number)
+                || !page_ordering_rules.page_numbers_are_after(&page_numbers_after, page_number)
+            {
+                are_page_numbers_in_proper_order = false;
+                break;
+            }
 
-                if !page_ordering_rules.page_numbers_are_before(&page_numbers_before, page_number)
-                    || !page_ordering_rules.page_numbers_are_after(&page_numbers_after, page_number)
-                {
-                    are_page_numbers_in_proper_order = false;
-                    break;
-                }
+            page_numbers_before.push(page_number);
+        }
 
-                page_numbers_before.push(page_number);
-            }
+        are_page_numbers_in_proper_order.then(|| u64::from(get_middle(page_ordering)))
+    });
 
-            are_page_numbers_in_proper_order.then_some(get_middle(page_ordering))
-        })
-        .sum()
+    Ok(checked_sum(middles)?.into())
 }
 
 #[aoc(day5, part2)]
-fn part2(input: &str) -> PageNumber {
-    let (rules, page_orderings) = parse(input);
-
-    let page_ordering_rules = PageOrderingRules::new_and_populate(&rules);
+fn part2(input: &str) -> Result<Answer, PageOrderingError> {
+    let (page_ordering_rules, page_orderings) = parse_rules(input)?;
 
-    let mut sum = 0;
+    let mut sum: u64 = 0;
 
-    for mut page_ordering in page_orderings {
+    for page_ordering in page_orderings {
         let page_numbers_in_proper_order = {
             let mut page_numbers_before: Vec<PageNumber> = Vec::new();
             let mut page_numbers_after: Vec<PageNumber> = page_ordering.iter().copied().collect();
@@ -193,20 +567,615 @@ fn part2(input: &str) -> PageNumber {
         };
 
         if !page_numbers_in_proper_order {
-            page_ordering.sort_by(|a: &u16, b| {
-                page_ordering_rules
-                    .is_page_number_before(*a, *b)
-                    .then(|| Ordering::Less)
-                    .or_else(|| Some(Ordering::Greater))
-                    .unwrap()
-            });
+            let page_ordering = page_ordering_rules.topological_order(&page_ordering)?;
 
-            sum = sum + get_middle(&page_ordering)
+            sum = checked_add(sum, u64::from(get_middle(&page_ordering)))?;
         }
     }
 
-    sum
+    Ok(sum.into())
+}
+
+pub struct UpdateAnalysis {
+    page_numbers: Vec<PageNumber>,
+    violated_rules: Vec<PageOrderingRule>,
+    repair: Result<UpdateRepair, PageOrderingError>,
+}
+
+impl UpdateAnalysis {
+    pub fn is_valid(&self) -> bool {
+        self.violated_rules.is_empty()
+    }
+}
+
+impl fmt::Display for UpdateAnalysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |page_numbers: &[PageNumber]| {
+            page_numbers
+                .iter()
+                .map(|p| p.to_string())
+                .collect::<Vec<String>>()
+                .join(",")
+        };
+
+        write!(f, "{}: ", join(&self.page_numbers))?;
+
+        if self.is_valid() {
+            return write!(f, "valid");
+        }
+
+        let violated_rules: Vec<String> = self
+            .violated_rules
+            .iter()
+            .map(|rule| format!("{}|{}", rule.page_before, rule.page_after))
+            .collect();
+
+        write!(f, "violates {}; ", violated_rules.join(" "))?;
+
+        match &self.repair {
+            Ok(repair) => {
+                let moves: Vec<String> = repair
+                    .moves
+                    .iter()
+                    .map(|m| format!("{} from index {} to {}", m.page_number, m.from_i, m.to_i))
+                    .collect();
+
+                write!(
+                    f,
+                    "{} move(s): {} -> {}",
+                    moves.len(),
+                    moves.join(", "),
+                    join(&repair.page_numbers)
+                )
+            }
+            Err(e) => write!(f, "cannot repair: {e}"),
+        }
+    }
+}
+
+pub fn analyze_updates(input: &str) -> Result<Vec<UpdateAnalysis>, InputError> {
+    let (page_ordering_rules, page_orderings) = parse_rules(input)?;
+
+    Ok(page_orderings
+        .into_iter()
+        .map(|page_numbers| UpdateAnalysis {
+            violated_rules: page_ordering_rules.violated_rules(&page_numbers),
+            repair: page_ordering_rules.minimal_repair(&page_numbers),
+            page_numbers,
+        })
+        .collect())
+}
+
+/// The index of an update and the chain of rules between its pages that orders two of them, if there is one
+pub type UpdateRulePath = (usize, Option<Vec<PageNumber>>);
+
+/// For every update with both pages: the shortest chain of rules between its pages that puts `page_number_before`
+/// before `page_number`
+pub fn find_rule_paths(
+    input: &str,
+    page_number_before: PageNumber,
+    page_number: PageNumber,
+) -> Result<Vec<UpdateRulePath>, InputError> {
+    let (page_ordering_rules, page_orderings) = parse_rules(input)?;
+
+    Ok(page_orderings
+        .iter()
+        .enumerate()
+        .filter(|(_, page_numbers)| {
+            page_numbers.contains(&page_number_before) && page_numbers.contains(&page_number)
+        })
+        .map(|(update_i, page_numbers)| {
+            (
+                update_i,
+                page_ordering_rules.rule_path(page_numbers, page_number_before, page_number),
+            )
+        })
+        .collect())
+}
+
+impl Explore for PageOrderingRules {
+    fn rules(&self, key: &str) -> QueryResult<'_> {
+        let page_number: PageNumber = key
+            .parse()
+            .map_err(|_| format!("{key:?} is not a page number"))?;
+
+        let sorted = |page_numbers: Option<&HashSet<PageNumber>>| {
+            let mut page_numbers: Vec<PageNumber> =
+                page_numbers.into_iter().flatten().copied().collect();
+            page_numbers.sort();
+            page_numbers
+        };
+
+        Ok(Box::new(BTreeMap::from([
+            (
+                "before",
+                sorted(self.page_numbers_before_map.get(&page_number)),
+            ),
+            (
+                "after",
+                sorted(self.page_numbers_after_map.get(&page_number)),
+            ),
+        ])))
+    }
+}
+
+/// The page ordering rules of `input` for `aoc repl`, where `rules 47` lists the pages that must come before and after
+/// page 47
+pub fn explore(input: &str) -> Result<Box<dyn Explore>, Box<dyn Error>> {
+    let (page_ordering_rules, _) = parse(input)?;
+
+    Ok(Box::new(PageOrderingRules::new_and_populate(
+        &page_ordering_rules,
+    )))
+}
+
+/// A puzzle input over `num_pages` distinct two digit page numbers (at most 90). Like the real inputs there is a rule
+/// for every pair of pages, so every update has exactly one correct order; the rules are acyclic and listed shuffled.
+/// About half of the updates, each an odd number of pages, are already in order.
+pub fn generate_input(seed: u64, num_pages: usize) -> String {
+    generate_input_with_updates(seed, num_pages, num_pages.clamp(3, 90))
+}
+
+fn generate_input_with_updates(seed: u64, num_pages: usize, num_updates: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut hidden_order: Vec<PageNumber> = (10..=99).collect();
+    hidden_order.shuffle(&mut rng);
+    hidden_order.truncate(num_pages.clamp(1, 90));
+
+    let mut rules: Vec<String> = Vec::new();
+
+    for (i, page_before) in hidden_order.iter().enumerate() {
+        for page_after in &hidden_order[i + 1..] {
+            rules.push(format!("{page_before}|{page_after}"));
+        }
+    }
+
+    rules.shuffle(&mut rng);
+
+    let max_update_len = hidden_order.len().min(23);
+
+    let updates: Vec<String> = (0..num_updates)
+        .map(|_| {
+            let len = rng.random_range(0..max_update_len.div_ceil(2)) * 2 + 1;
+
+            let mut update: Vec<PageNumber> = hidden_order.clone();
+            update.shuffle(&mut rng);
+            update.truncate(len);
+
+            if rng.random_bool(0.5) {
+                update.sort_by_key(|p| hidden_order.iter().position(|o| o == p));
+            }
+
+            update
+                .iter()
+                .map(|p| p.to_string())
+                .collect::<Vec<String>>()
+                .join(",")
+        })
+        .collect();
+
+    format!("{}\n\n{}", rules.join("\n"), updates.join("\n"))
+}
+
+/// A synthetic input with as many pages and updates as `input`, for `aoc synthesize`
+pub fn synthesize(input: &str, seed: u64) -> Result<String, Box<dyn Error>> {
+    let (page_ordering_rules, page_orderings) = parse(input)?;
+
+    let page_numbers: HashSet<PageNumber> = page_ordering_rules
+        .iter()
+        .flat_map(|rule| [rule.page_before, rule.page_after])
+        .chain(page_orderings.iter().flatten().copied())
+        .collect();
+
+    Ok(generate_input_with_updates(
+        seed,
+        page_numbers.len(),
+        page_orderings.len(),
+    ))
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47";
+
+    fn page_ordering_rules(rules: &[(PageNumber, PageNumber)]) -> PageOrderingRules {
+        let rules: Vec<PageOrderingRule> = rules
+            .iter()
+            .map(|(page_before, page_after)| PageOrderingRule {
+                page_before: *page_before,
+                page_after: *page_after,
+            })
+            .collect();
+
+        PageOrderingRules::new_and_populate(&rules)
+    }
+
+    // an update is in order when no rule puts a later page before an earlier one
+    fn reference_is_in_order(rules: &[PageOrderingRule], update: &[PageNumber]) -> bool {
+        (0..update.len()).all(|i| {
+            (i + 1..update.len()).all(|j| {
+                !rules
+                    .iter()
+                    .any(|r| r.page_before == update[j] && r.page_after == update[i])
+            })
+        })
+    }
+
+    fn permutations(pages: &[PageNumber]) -> Vec<Vec<PageNumber>> {
+        if pages.is_empty() {
+            return vec![Vec::new()];
+        }
+
+        (0..pages.len())
+            .flat_map(|i| {
+                let mut rest = pages.to_vec();
+                let first = rest.remove(i);
+
+                permutations(&rest).into_iter().map(move |mut p| {
+                    p.insert(0, first);
+                    p
+                })
+            })
+            .collect()
+    }
+
+    fn reference_part1(input: &str) -> PageNumber {
+        let (rules, updates) = parse(input).unwrap();
+
+        updates
+            .iter()
+            .filter(|update| reference_is_in_order(&rules, update))
+            .map(|update| update[update.len() / 2])
+            .sum()
+    }
+
+    // every order of an out of order update is tried until one breaks no rule
+    fn reference_part2(input: &str) -> PageNumber {
+        let (rules, updates) = parse(input).unwrap();
+
+        updates
+            .iter()
+            .filter(|update| !reference_is_in_order(&rules, update))
+            .map(|update| {
+                let ordered = permutations(update)
+                    .into_iter()
+                    .find(|p| reference_is_in_order(&rules, p))
+                    .unwrap();
+
+                ordered[ordered.len() / 2]
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_parts_match_reference_on_generated_inputs() {
+        for seed in 0..2_000 {
+            let input = generate_input(seed, 2 + seed as usize % 6);
+
+            assert_eq!(
+                part1(&input),
+                Ok(Answer::from(reference_part1(&input))),
+                "seed {seed}"
+            );
+            assert_eq!(
+                part2(&input),
+                Ok(Answer::from(reference_part2(&input))),
+                "seed {seed}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_generate_input() {
+        let input = generate_input(11, 49);
+        let (rules, updates) = parse(&input).unwrap();
+
+        assert_eq!(input, generate_input(11, 49));
+        assert_eq!(rules.len(), 49 * 48 / 2);
+        assert!(updates.iter().all(|update| update.len() % 2 == 1));
+        assert!(
+            updates
+                .iter()
+                .any(|update| reference_is_in_order(&rules, update))
+        );
+        assert!(
+            updates
+                .iter()
+                .any(|update| !reference_is_in_order(&rules, update))
+        );
+    }
+
+    #[test]
+    fn test_example() {
+        assert_eq!(part1(EXAMPLE), Ok(Answer::from(143)));
+        assert_eq!(part2(EXAMPLE), Ok(Answer::from(123)));
+    }
+
+    #[test]
+    fn test_sums_past_page_number() {
+        let updates = "60000,60001,60002\n60000,60001,60002";
+
+        assert_eq!(
+            part1(&format!(
+                "60000|60001\n60001|60002\n60000|60002\n\n{updates}"
+            )),
+            Ok(Answer::from(120_002))
+        );
+        assert_eq!(
+            part2(&format!(
+                "60002|60001\n60001|60000\n60002|60000\n\n{updates}"
+            )),
+            Ok(Answer::from(120_002))
+        );
+    }
+
+    #[test]
+    fn test_topological_order() {
+        let rules = page_ordering_rules(&[(1, 2), (2, 3), (1, 3), (3, 4), (2, 4), (1, 4)]);
+
+        assert_eq!(rules.topological_order(&[4, 2, 3, 1]), Ok(vec![1, 2, 3, 4]));
+        assert_eq!(rules.topological_order(&[3, 2]), Ok(vec![2, 3]));
+    }
+
+    #[test]
+    fn test_topological_order_ambiguous() {
+        let rules = page_ordering_rules(&[(1, 2), (1, 3)]);
+
+        assert_eq!(
+            rules.topological_order(&[3, 2, 1]),
+            Err(PageOrderingError::Ambiguous(3, 2))
+        );
+        assert_eq!(
+            rules.topological_order(&[1, 5]),
+            Err(PageOrderingError::Ambiguous(1, 5))
+        );
+    }
+
+    #[test]
+    fn test_topological_order_cycle() {
+        let rules = page_ordering_rules(&[(1, 2), (2, 3), (3, 4), (4, 2)]);
+
+        assert_eq!(
+            rules.topological_order(&[4, 3, 2, 1]),
+            Err(PageOrderingError::Cycle(vec![4, 2, 3, 4]))
+        );
+        assert_eq!(
+            PageOrderingError::Cycle(vec![2, 3, 4, 2]).to_string(),
+            "page ordering rules form a cycle: 2 -> 3 -> 4 -> 2"
+        );
+    }
+
+    #[test]
+    fn test_part2_reports_errors() {
+        assert_eq!(
+            part2("1|2\n2|3\n3|1\n\n3,2,1"),
+            Err(PageOrderingError::Cycle(vec![3, 1, 2, 3]))
+        );
+        assert_eq!(
+            part2("1|2\n1|3\n\n3,2,1"),
+            Err(PageOrderingError::Ambiguous(3, 2))
+        );
+    }
+
+    #[test]
+    fn test_rule_path() {
+        let rules = page_ordering_rules(&[(1, 2), (2, 3), (3, 4), (1, 5)]);
+        let pages = [1, 2, 3, 4, 5];
+
+        assert_eq!(rules.rule_path(&pages, 1, 4), Some(vec![1, 2, 3, 4]));
+        assert_eq!(rules.rule_path(&pages, 1, 5), Some(vec![1, 5]));
+        assert_eq!(rules.rule_path(&pages, 4, 1), None);
+        assert_eq!(rules.rule_path(&pages, 2, 4), Some(vec![2, 3, 4]));
+        assert_eq!(rules.rule_path(&pages, 5, 4), None);
+        assert_eq!(rules.rule_path(&[1, 3, 4], 1, 4), None);
+    }
+
+    #[test]
+    fn test_rule_path_in_cyclic_rules() {
+        // 1, 2 and 3 form a cycle, as the rules of real inputs do, so every page of it reaches every other one
+        let rules = page_ordering_rules(&[(1, 2), (2, 3), (3, 1), (3, 4)]);
+
+        assert_eq!(rules.rule_path(&[1, 2, 3, 4], 1, 4), Some(vec![1, 2, 3, 4]));
+        assert_eq!(rules.rule_path(&[1, 2, 3], 2, 1), Some(vec![2, 3, 1]));
+
+        // within these updates nothing orders the two pages
+        assert_eq!(rules.rule_path(&[1, 4], 1, 4), None);
+        assert_eq!(rules.rule_path(&[2, 1], 2, 1), None);
+        assert_eq!(rules.rule_path(&[4, 2, 1], 2, 1), None);
+
+        assert_eq!(
+            find_rule_paths("1|2\n2|3\n3|1\n3|4\n\n1,4\n1,2,3,4\n2,3", 1, 4),
+            Ok(vec![(0, None), (1, Some(vec![1, 2, 3, 4]))])
+        );
+    }
+
+    #[test]
+    fn test_violated_rules() {
+        let rules = page_ordering_rules(&[(1, 2), (2, 3), (1, 3)]);
+
+        assert_eq!(
+            rules.violated_rules(&[3, 1, 2]),
+            vec![
+                PageOrderingRule {
+                    page_before: 1,
+                    page_after: 3
+                },
+                PageOrderingRule {
+                    page_before: 2,
+                    page_after: 3
+                },
+            ]
+        );
+        assert_eq!(rules.violated_rules(&[1, 2, 3]), vec![]);
+    }
+
+    #[test]
+    fn test_minimal_repair() {
+        let rules = page_ordering_rules(&[(1, 2), (2, 3), (3, 4), (4, 5)]);
+
+        assert_eq!(
+            rules.minimal_repair(&[1, 5, 2, 3, 4]),
+            Ok(UpdateRepair {
+                moves: vec![PageMove {
+                    page_number: 5,
+                    from_i: 1,
+                    to_i: 4
+                }],
+                page_numbers: vec![1, 2, 3, 4, 5],
+            })
+        );
+
+        let analyses = analyze_updates(EXAMPLE).unwrap();
+
+        assert_eq!(
+            analyses[3].to_string(),
+            "75,97,47,61,53: violates 97|75; 1 move(s): 75 from index 0 to 1 -> 97,75,47,61,53"
+        );
+        assert_eq!(analyses[0].to_string(), "75,47,61,53,29: valid");
+        assert_eq!(
+            analyses[5].to_string(),
+            "97,13,75,29,47: violates 75|13 29|13 47|13 47|29; 2 move(s): 13 from index 1 to 4, 29 from index 3 to 3 -> 97,75,47,29,13"
+        );
+    }
+
+    // whether the rules force update[i] before update[j] through pages of the update only; a rule chain through a page
+    // the update leaves out does not order it
+    fn is_forced_before(
+        rules: &PageOrderingRules,
+        update: &[PageNumber],
+        i: usize,
+        j: usize,
+    ) -> bool {
+        let mut is_reached = vec![false; update.len()];
+        let mut stack = vec![i];
+
+        while let Some(cur_i) = stack.pop() {
+            for next_i in 0..update.len() {
+                if !is_reached[next_i] && rules.is_page_number_before(update[cur_i], update[next_i])
+                {
+                    is_reached[next_i] = true;
+                    stack.push(next_i);
+                }
+            }
+        }
+
+        is_reached[j]
+    }
+
+    #[test]
+    fn test_minimal_repair_matches_brute_force() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut num_ordered_through_missing_pages = 0;
+
+        for _ in 0..500 {
+            let num_pages = rng.random_range(1..=8);
+
+            // a hidden order makes the rules acyclic; only some of its pairs become rules
+            let mut hidden_order: Vec<PageNumber> = (1..=num_pages as PageNumber).collect();
+            hidden_order.shuffle(&mut rng);
+
+            let mut rule_pairs = Vec::new();
+            for i in 0..num_pages {
+                for j in i + 1..num_pages {
+                    if rng.random_ratio(1, 3) {
+                        rule_pairs.push((hidden_order[i], hidden_order[j]));
+                    }
+                }
+            }
+
+            let rules = page_ordering_rules(&rule_pairs);
+
+            // often only some of the pages, so that rule chains through the missing ones must not count
+            let mut update: Vec<PageNumber> = hidden_order.clone();
+            update.shuffle(&mut rng);
+            update.truncate(rng.random_range(1..=num_pages));
+
+            let n = update.len();
+
+            num_ordered_through_missing_pages += (0..n)
+                .flat_map(|i| (0..n).map(move |j| (i, j)))
+                .filter(|(i, j)| {
+                    assert_eq!(
+                        rules.rule_path(&update, update[*i], update[*j]).is_some(),
+                        is_forced_before(&rules, &update, *i, *j)
+                    );
+
+                    rules
+                        .rule_path(&hidden_order, update[*i], update[*j])
+                        .is_some()
+                        && !is_forced_before(&rules, &update, *i, *j)
+                })
+                .count();
+
+            // the most pages that can stay where they are, checked over every subset
+            let max_kept = (0..1u32 << n)
+                .filter(|subset| {
+                    let kept: Vec<usize> = (0..n).filter(|i| subset & (1 << i) != 0).collect();
+
+                    kept.iter().enumerate().all(|(a, i)| {
+                        kept[a + 1..]
+                            .iter()
+                            .all(|j| !is_forced_before(&rules, &update, *j, *i))
+                    })
+                })
+                .map(|subset| subset.count_ones() as usize)
+                .max()
+                .unwrap();
+
+            let repair = rules.minimal_repair(&update).unwrap();
+
+            assert_eq!(
+                repair.moves.len(),
+                n - max_kept,
+                "{update:?} {rule_pairs:?}"
+            );
+            assert_eq!(rules.violated_rules(&repair.page_numbers), vec![]);
+
+            let kept_page_numbers: Vec<PageNumber> = update
+                .iter()
+                .copied()
+                .filter(|p| !repair.moves.iter().any(|m| m.page_number == *p))
+                .collect();
+            let repaired_kept_page_numbers: Vec<PageNumber> = repair
+                .page_numbers
+                .iter()
+                .copied()
+                .filter(|p| kept_page_numbers.contains(p))
+                .collect();
+
+            assert_eq!(kept_page_numbers, repaired_kept_page_numbers);
+        }
+
+        assert!(num_ordered_through_missing_pages > 0);
+    }
+}
@@ -1,15 +1,23 @@
 use std::{
     cmp::{max, min},
     collections::HashSet,
+    error::Error,
     panic::{self, catch_unwind},
 };
 
 use aoc_runner_derive::aoc;
+use aoc_tools::{
+    cached,
+    input::InputError,
+    repl::{Explore, QueryResult},
+};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use serde::{Deserialize, Serialize};
 
 type XCoordinate = usize;
 type YCoordinate = usize;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 enum GuardDirection {
     UP,
     DOWN,
@@ -26,9 +34,38 @@ impl GuardDirection {
             GuardDirection::LEFT => GuardDirection::UP,
         }
     }
+
+    fn from_char(c: char) -> Option<GuardDirection> {
+        match c {
+            '^' => Some(GuardDirection::UP),
+            'v' => Some(GuardDirection::DOWN),
+            '<' => Some(GuardDirection::LEFT),
+            '>' => Some(GuardDirection::RIGHT),
+            _ => None,
+        }
+    }
+
+    fn as_char(&self) -> char {
+        match self {
+            GuardDirection::UP => '^',
+            GuardDirection::DOWN => 'v',
+            GuardDirection::LEFT => '<',
+            GuardDirection::RIGHT => '>',
+        }
+    }
+
+    // x is the row and y the column
+    fn x_y_offset(&self) -> (isize, isize) {
+        match self {
+            GuardDirection::UP => (-1, 0),
+            GuardDirection::DOWN => (1, 0),
+            GuardDirection::LEFT => (0, -1),
+            GuardDirection::RIGHT => (0, 1),
+        }
+    }
 }
 
-#[derive(Clone, Copy, Hash, PartialEq, Debug)]
+#[derive(Clone, Copy, Hash, PartialEq, Debug, Serialize, Deserialize)]
 struct Coordinate {
     x: XCoordinate,
     y: YCoordinate,
@@ -36,7 +73,7 @@ struct Coordinate {
 
 impl Eq for Coordinate {}
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 struct Obstacle {
     coordinate: Coordinate,
     obstacle_to_start: Option<Coordinate>,
@@ -44,6 +81,7 @@ struct Obstacle {
     is_obstacle: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
 struct Map {
     rows: Vec<Vec<Obstacle>>,
     cols: Vec<Vec<Obstacle>>,
@@ -161,7 +199,7 @@ impl Map {
             }
         };
 
-        let obstacle_before = cols.get(y - 1);
+        let obstacle_before = y.checked_sub(1).and_then(|y| cols.get(y));
         let obstacle_after = cols.get(y + 1);
 
         let current_obstacle = Obstacle {
@@ -190,7 +228,7 @@ impl Map {
             }
         };
 
-        let obstacle_before = rows.get(x - 1);
+        let obstacle_before = x.checked_sub(1).and_then(|x| rows.get(x));
         let obstacle_after = rows.get(x + 1);
 
         let current_obstacle = Obstacle {
@@ -253,40 +291,196 @@ impl Map {
     }
 }
 
-fn parse(input: &str) -> (Map, Coordinate, GuardDirection) {
+// building both axes of the map is most of the work, so `--cache` keeps it between runs
+#[cached]
+fn parse(input: &str) -> Result<(Map, Coordinate, GuardDirection), InputError> {
     let mut map = Map::new();
     let mut guard_coordinate: Option<Coordinate> = None;
 
-    input.lines().enumerate().for_each(|(x, line)| {
-        line.split("")
-            .filter(|c| !c.is_empty())
-            .enumerate()
-            .for_each(|(y, c)| {
-                if c == "#" {
-                    map.add_obstacle(x, y);
-                } else if c == "^" {
+    let num_cols = input.lines().next().map_or(0, |line| line.chars().count());
+
+    for (x, line) in input.lines().enumerate() {
+        if line.chars().count() != num_cols {
+            return Err(InputError::at_line(
+                x,
+                format!("every row must be {num_cols} cells long like the first"),
+            ));
+        }
+
+        for (y, c) in line.chars().enumerate() {
+            match c {
+                '#' => map.add_obstacle(x, y),
+                '^' if guard_coordinate.is_none() => {
                     guard_coordinate = Some(Coordinate { x, y });
                     map.add_empty(x, y);
-                } else {
-                    map.add_empty(x, y);
                 }
-            });
+                '^' => return Err(InputError::at_line(x, "there is more than one guard")),
+                '.' => map.add_empty(x, y),
+                c => return Err(InputError::at_line(x, format!("unexpected {c:?}"))),
+            }
+        }
+    }
+
+    let guard_coordinate =
+        guard_coordinate.ok_or_else(|| InputError::new("there is no guard (^) on the map"))?;
+
+    return Ok((map, guard_coordinate, GuardDirection::UP));
+}
+
+impl Explore for Map {
+    fn cell(&self, row: usize, column: usize) -> QueryResult<'_> {
+        self.rows
+            .get(row)
+            .and_then(|cells| cells.get(column))
+            .map(|obstacle| Box::new(obstacle) as Box<dyn std::fmt::Debug>)
+            .ok_or_else(|| format!("{row} {column} is outside the map"))
+    }
+
+    fn neighbours(&self, row: usize, column: usize) -> QueryResult<'_> {
+        self.cell(row, column)?;
+
+        let neighbours: Vec<(GuardDirection, &Obstacle)> = [
+            GuardDirection::UP,
+            GuardDirection::RIGHT,
+            GuardDirection::DOWN,
+            GuardDirection::LEFT,
+        ]
+        .into_iter()
+        .filter_map(|direction| {
+            let (x_offset, y_offset) = direction.x_y_offset();
+            let (x, y) = (
+                row.checked_add_signed(x_offset)?,
+                column.checked_add_signed(y_offset)?,
+            );
+
+            Some((direction, self.rows.get(x)?.get(y)?))
+        })
+        .collect();
+
+        Ok(Box::new(neighbours))
+    }
+}
+
+/// The map of `input` for `aoc repl`, where `cell` and `neighbours` show the obstacles the guard would walk into from a
+/// cell
+pub fn explore(input: &str) -> Result<Box<dyn Explore>, Box<dyn Error>> {
+    let (map, _, _) = parse(input)?;
+
+    Ok(Box::new(map))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GuardStep {
+    coordinate: Coordinate,
+    direction: GuardDirection,
+}
+
+impl GuardStep {
+    pub fn row_col(&self) -> (usize, usize) {
+        (self.coordinate.x, self.coordinate.y)
+    }
+
+    pub fn guard_char(&self) -> char {
+        self.direction.as_char()
+    }
+}
+
+pub struct GuardWalk {
+    steps: Vec<GuardStep>,
+    is_loop: bool,
+}
+
+impl GuardWalk {
+    pub fn steps(&self) -> &[GuardStep] {
+        &self.steps
+    }
+
+    /// Whether the guard ended up back in a position and direction they had already been in
+    pub fn is_loop(&self) -> bool {
+        self.is_loop
+    }
+
+    pub fn num_visited(&self) -> usize {
+        self.steps
+            .iter()
+            .map(|step| step.coordinate)
+            .collect::<HashSet<Coordinate>>()
+            .len()
+    }
+}
+
+/// Walks the guard one cell at a time over the map, turning right in front of every `#`, until they leave the map or
+/// start going in circles
+pub fn guard_walk(input: &str) -> GuardWalk {
+    let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+
+    walk_grid(&grid)
+}
+
+fn walk_grid(grid: &[Vec<char>]) -> GuardWalk {
+    let guard_step = grid.iter().enumerate().find_map(|(x, row)| {
+        row.iter().enumerate().find_map(|(y, c)| {
+            GuardDirection::from_char(*c).map(|direction| GuardStep {
+                coordinate: Coordinate { x, y },
+                direction,
+            })
+        })
     });
 
-    return (map, guard_coordinate.unwrap(), GuardDirection::UP);
+    let mut walk = GuardWalk {
+        steps: Vec::new(),
+        is_loop: false,
+    };
+    let mut seen_steps: HashSet<(Coordinate, GuardDirection)> = HashSet::new();
+
+    let Some(mut guard_step) = guard_step else {
+        return walk;
+    };
+
+    loop {
+        if !seen_steps.insert((guard_step.coordinate, guard_step.direction)) {
+            walk.is_loop = true;
+            return walk;
+        }
+
+        walk.steps.push(guard_step);
+
+        let (x_offset, y_offset) = guard_step.direction.x_y_offset();
+        let next_coordinate = guard_step
+            .coordinate
+            .x
+            .checked_add_signed(x_offset)
+            .zip(guard_step.coordinate.y.checked_add_signed(y_offset))
+            .filter(|(x, y)| grid.get(*x).is_some_and(|row| *y < row.len()));
+
+        match next_coordinate {
+            None => return walk,
+            Some((x, y)) if grid[x][y] == '#' => {
+                guard_step.direction = guard_step.direction.turn();
+            }
+            Some((x, y)) => guard_step.coordinate = Coordinate { x, y },
+        }
+    }
 }
 
 #[aoc(day6, part1)]
-fn part1(input: &str) -> usize {
-    let (map, gaurd_coordinate, mut gaurd_direction) = parse(input);
+fn part1(input: &str) -> Result<usize, InputError> {
+    let (map, gaurd_coordinate, mut gaurd_direction) = parse(input)?;
 
     let mut gaurd_coordinate = Some(gaurd_coordinate);
 
     let mut coordinates_visited: HashSet<Coordinate> = HashSet::new();
+    let mut turns_taken: HashSet<(Coordinate, GuardDirection)> = HashSet::new();
 
     while gaurd_coordinate.is_some() {
         let current_gaurd_coordinate = gaurd_coordinate.unwrap();
 
+        if !turns_taken.insert((current_gaurd_coordinate, gaurd_direction)) {
+            return Err(InputError::new(
+                "the guard walks in a loop and never leaves the map",
+            ));
+        }
+
         let next_gaurd_coordinate_opional = map.get_coordinate_infront_of_obstacle(
             current_gaurd_coordinate.x,
             current_gaurd_coordinate.y,
@@ -308,7 +502,7 @@ fn part1(input: &str) -> usize {
             }),
             GuardDirection::RIGHT => next_gaurd_coordinate_opional.unwrap_or_else(|| Coordinate {
                 x: current_gaurd_coordinate.x,
-                y: map.cols.len(),
+                y: map.cols.len() - 1,
             }),
         };
 
@@ -336,12 +530,75 @@ fn part1(input: &str) -> usize {
         gaurd_direction = gaurd_direction.turn();
     }
 
-    return coordinates_visited.len();
+    return Ok(coordinates_visited.len());
 }
 
+// an obstruction can only change the guard's walk on a cell they would otherwise walk over, so only those are tried
 #[aoc(day6, part2)]
-fn part2(input: &str) -> usize {
-    return 123;
+fn part2(input: &str) -> Result<usize, InputError> {
+    parse(input)?;
+
+    let mut grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+    let walk = walk_grid(&grid);
+
+    if walk.is_loop() {
+        return Err(InputError::new(
+            "the guard walks in a loop and never leaves the map",
+        ));
+    }
+
+    let start = walk.steps[0].coordinate;
+    let candidates: HashSet<Coordinate> = walk
+        .steps
+        .iter()
+        .map(|step| step.coordinate)
+        .filter(|coordinate| *coordinate != start)
+        .collect();
+
+    let num_looping = candidates
+        .into_iter()
+        .filter(|coordinate| {
+            grid[coordinate.x][coordinate.y] = '#';
+            let is_loop = walk_grid(&grid).is_loop();
+            grid[coordinate.x][coordinate.y] = '.';
+
+            is_loop
+        })
+        .count();
+
+    Ok(num_looping)
+}
+
+/// A puzzle input: a map of `num_rows` by `num_cols` cells with roughly one obstacle in eight cells and the guard facing
+/// up on one of the empty cells
+pub fn generate_input(seed: u64, num_rows: usize, num_cols: usize) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let (num_rows, num_cols) = (num_rows.max(1), num_cols.max(1));
+
+    let mut grid: Vec<Vec<char>> = (0..num_rows)
+        .map(|_| {
+            (0..num_cols)
+                .map(|_| if rng.random_bool(0.125) { '#' } else { '.' })
+                .collect()
+        })
+        .collect();
+
+    let (guard_x, guard_y) = (rng.random_range(0..num_rows), rng.random_range(0..num_cols));
+    grid[guard_x][guard_y] = GuardDirection::UP.as_char();
+
+    grid.iter()
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// A synthetic map with as many rows and columns as the map of `input`, for `aoc synthesize`
+pub fn synthesize(input: &str, seed: u64) -> Result<String, Box<dyn Error>> {
+    parse(input)?;
+
+    let num_cols = input.lines().next().map_or(0, |line| line.chars().count());
+
+    Ok(generate_input(seed, input.lines().count(), num_cols))
 }
 
 #[cfg(test)]
@@ -349,4 +606,165 @@ mod tests {
     use super::*;
 
     // TODO: add tests on Map to verify that inserting works as expected
+
+    const EXAMPLE: &str = "....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#...";
+
+    // the guard's cells when walking until they leave; None when they are still on the map after more moves than
+    // there are cell and direction pairs, since then they must be going in circles
+    fn reference_visited(grid: &[Vec<char>]) -> Option<usize> {
+        let (mut row, mut col) = (0..grid.len())
+            .flat_map(|row| (0..grid[row].len()).map(move |col| (row, col)))
+            .find(|(row, col)| grid[*row][*col] == '^')
+            .unwrap();
+        let (mut row_step, mut col_step) = (-1, 0);
+
+        let mut visited = HashSet::from([(row, col)]);
+
+        for _ in 0..=4 * grid.len() * grid[0].len() {
+            let (next_row, next_col) = (row as isize + row_step, col as isize + col_step);
+
+            if next_row < 0
+                || next_col < 0
+                || next_row as usize >= grid.len()
+                || next_col as usize >= grid[0].len()
+            {
+                return Some(visited.len());
+            }
+
+            if grid[next_row as usize][next_col as usize] == '#' {
+                (row_step, col_step) = (col_step, -row_step);
+            } else {
+                (row, col) = (next_row as usize, next_col as usize);
+                visited.insert((row, col));
+            }
+        }
+
+        None
+    }
+
+    fn reference_part1(input: &str) -> usize {
+        let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+
+        reference_visited(&grid).unwrap_or(0)
+    }
+
+    // an obstruction is tried on every empty cell
+    fn reference_part2(input: &str) -> usize {
+        let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+
+        (0..grid.len())
+            .flat_map(|row| (0..grid[row].len()).map(move |col| (row, col)))
+            .filter(|(row, col)| grid[*row][*col] == '.')
+            .filter(|(row, col)| {
+                let mut grid = grid.clone();
+                grid[*row][*col] = '#';
+                reference_visited(&grid).is_none()
+            })
+            .count()
+    }
+
+    #[test]
+    fn test_reference_example() {
+        let mut grid: Vec<Vec<char>> = EXAMPLE.lines().map(|line| line.chars().collect()).collect();
+
+        assert_eq!(reference_part1(EXAMPLE), 41);
+        assert_eq!(reference_part2(EXAMPLE), 6);
+
+        grid[6][3] = '#';
+        assert_eq!(reference_visited(&grid), None);
+    }
+
+    #[test]
+    fn test_guard_walk_matches_reference_on_generated_inputs() {
+        for seed in 0..2_000 {
+            let input = generate_input(seed, 3 + seed as usize % 10, 3 + seed as usize / 10 % 7);
+            let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+
+            let walk = guard_walk(&input);
+
+            assert_eq!(
+                (!walk.is_loop()).then(|| walk.num_visited()),
+                reference_visited(&grid),
+                "seed {seed}:\n{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_part1_matches_reference_on_generated_inputs() {
+        for seed in 0..2_000 {
+            let input = generate_input(seed, 3 + seed as usize % 10, 3 + seed as usize / 10 % 7);
+
+            let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+
+            // a guard that never leaves is an error for part1
+            assert_eq!(
+                part1(&input).ok(),
+                reference_visited(&grid),
+                "seed {seed}:\n{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_part2_example() {
+        assert_eq!(part2(EXAMPLE).unwrap(), 6);
+    }
+
+    #[test]
+    fn test_part2_matches_reference_on_generated_inputs() {
+        for seed in 0..500 {
+            let input = generate_input(seed, 3 + seed as usize % 10, 3 + seed as usize / 10 % 7);
+
+            let grid: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
+
+            // as for part1, a guard that never leaves is an error
+            assert_eq!(
+                part2(&input).ok(),
+                reference_visited(&grid).map(|_| reference_part2(&input)),
+                "seed {seed}:\n{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_synthesize() {
+        let input = "...#..\n......\n..^...";
+        let synthetic = synthesize(input, 3).unwrap();
+
+        assert_eq!(synthetic.lines().count(), 3);
+        assert!(synthetic.lines().all(|line| line.len() == 6));
+        assert_eq!(synthetic.matches('^').count(), 1);
+    }
+
+    #[test]
+    fn test_guard_walk() {
+        let walk = guard_walk(EXAMPLE);
+
+        assert!(!walk.is_loop());
+        assert_eq!(walk.num_visited(), 41);
+        assert_eq!(walk.steps()[0].row_col(), (6, 4));
+        assert_eq!(walk.steps()[0].guard_char(), '^');
+        assert_eq!(walk.steps().last().unwrap().row_col(), (9, 7));
+        assert_eq!(walk.steps().last().unwrap().guard_char(), 'v');
+    }
+
+    #[test]
+    fn test_guard_walk_loop() {
+        let mut grid: Vec<Vec<char>> = EXAMPLE.lines().map(|line| line.chars().collect()).collect();
+        grid[6][3] = '#';
+
+        let input: Vec<String> = grid.iter().map(|row| row.iter().collect()).collect();
+
+        assert!(guard_walk(&input.join("\n")).is_loop());
+    }
 }
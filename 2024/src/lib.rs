@@ -1,8 +1,8 @@
-mod day6;
-mod day5;
-mod day4;
-mod day3;
-mod day2;
+pub mod day6;
+pub mod day5;
+pub mod day4;
+pub mod day3;
+pub mod day2;
 use aoc_runner_derive::aoc_lib;
 
 mod day1;
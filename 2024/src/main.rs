@@ -1,5 +1,46 @@
-use aoc_runner_derive::aoc_main;
+use std::process::ExitCode;
 
-extern crate advent_of_code_2024;
+use advent_of_code_2024::{day2, day3, day5, day6};
+use aoc_tools::{alloc::CountingAllocator, aoc_solutions, runner::Runner};
 
-aoc_main! { lib = advent_of_code_2024 }
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn main() -> ExitCode {
+    Runner::new(
+        advent_of_code_2024::YEAR,
+        aoc_solutions! { lib = advent_of_code_2024 },
+    )
+    .explore(5, day5::explore)
+    .explore(6, day6::explore)
+    .synthesizer(2, day2::synthesize)
+    .synthesizer(3, day3::synthesize)
+    .synthesizer(5, day5::synthesize)
+    .synthesizer(6, day6::synthesize)
+    .main()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use aoc_tools::{aoc_solutions, fuzz};
+
+    // what the fuzz target in fuzz/ does, on a small deterministic scale so that it runs on stable with every test run
+    #[test]
+    fn test_solutions_do_not_panic_on_mutated_fixtures() {
+        let solutions = aoc_solutions! { lib = advent_of_code_2024 };
+
+        for (seed_i, (name, seed)) in fuzz::seed_corpus(Path::new("fixtures"))
+            .unwrap()
+            .iter()
+            .enumerate()
+        {
+            let inputs = fuzz::mutations(seed, 300, seed_i as u64);
+
+            if let Some(panic) = fuzz::find_panic(&solutions, &inputs) {
+                panic!("{name}: {panic}");
+            }
+        }
+    }
+}
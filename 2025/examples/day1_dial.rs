@@ -0,0 +1,65 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use aoc_2025::{
+    YEAR,
+    day1::{DialOptions, count_zeros},
+};
+use aoc_tools::config::Config;
+
+const USAGE: &str = "usage: day1_dial [--size <positions>] [--start <number>] [input path]";
+
+fn parse_number(arg: Option<String>) -> i64 {
+    arg.and_then(|n| n.parse::<i64>().ok()).unwrap_or_else(|| {
+        eprintln!("{USAGE}");
+        process::exit(2);
+    })
+}
+
+fn main() {
+    let mut options = DialOptions::default();
+    let mut input_path: Option<PathBuf> = None;
+
+    let mut args = env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => options.num_positions = parse_number(args.next()),
+            "--start" => options.start_position = parse_number(args.next()),
+            _ if arg.starts_with("--") => {
+                eprintln!("{USAGE}");
+                process::exit(2);
+            }
+            _ => input_path = Some(PathBuf::from(arg)),
+        }
+    }
+
+    // where the runner reads this day's input from, unless another input is given
+    let input_path = input_path.unwrap_or_else(|| {
+        Config::load(Path::new(""))
+            .map(|config| config.input_path(YEAR, 1))
+            .unwrap_or_else(|e| {
+                eprintln!("{e}");
+                process::exit(1);
+            })
+    });
+
+    let input = fs::read_to_string(&input_path).unwrap_or_else(|e| {
+        eprintln!("failed to read {}: {e}", input_path.display());
+        process::exit(1);
+    });
+
+    match count_zeros(&input, options) {
+        Ok((num_zero_landings, num_zero_clicks)) => {
+            println!("rotations ending on 0: {num_zero_landings}");
+            println!("clicks on 0: {num_zero_clicks}");
+        }
+        Err(e) => {
+            eprintln!("{}: {e}", input_path.display());
+            process::exit(1);
+        }
+    }
+}
@@ -2,6 +2,10 @@ use core::num;
 use std::sync::LazyLock;
 
 use aoc_runner_derive::{aoc, aoc_generator};
+use aoc_tools::{
+    answer::{Answer, Overflow, checked_add},
+    input::{InputError, parse_number},
+};
 use regex::Regex;
 
 #[derive(Debug)]
@@ -10,73 +14,97 @@ enum DialDirection {
     RIGHT,
 }
 
+#[derive(Debug)]
 struct DialRotation {
     direction: DialDirection,
     num_clicks: i32,
 }
 
-struct Dial {
-    dial_number: i16,
+/// The size of a dial and the number it starts at; the puzzle's dial has 100 positions and starts at 50
+#[derive(Clone, Copy, Debug)]
+pub struct DialOptions {
+    pub num_positions: i64,
+    pub start_position: i64,
 }
 
-impl Dial {
-    fn new() -> Dial {
-        Dial { dial_number: 50 }
-    }
-
-    fn number_full_rotations(num_clicks: i32) -> i32 {
-        (num_clicks / 100).try_into().unwrap()
-    }
-
-    fn actual_num_clicks(num_clicks: i32) -> i16 {
-        if num_clicks > 99 {
-            return (num_clicks % 100).try_into().unwrap();
+impl Default for DialOptions {
+    fn default() -> Self {
+        DialOptions {
+            num_positions: 100,
+            start_position: 50,
         }
-
-        return num_clicks.try_into().unwrap();
     }
+}
 
-    fn rotate_left(&mut self, num_clicks: i32) {
-        let actual_num_clicks = Dial::actual_num_clicks(num_clicks);
+struct Dial {
+    num_positions: i64,
+    dial_number: i64,
+}
 
-        let mut new_dial_number = self.dial_number - actual_num_clicks;
+struct DialRotationResult {
+    // how many clicks of the rotation left the dial pointing at 0, including the last one
+    num_zero_clicks: i64,
+    is_on_zero: bool,
+}
 
-        if new_dial_number < 0 {
-            new_dial_number = 100 + new_dial_number
+impl Dial {
+    fn new(options: DialOptions) -> Result<Dial, String> {
+        let DialOptions {
+            num_positions,
+            start_position,
+        } = options;
+
+        if num_positions <= 0 {
+            return Err(format!(
+                "a dial needs at least one position, not {num_positions}"
+            ));
         }
 
-        self.dial_number = new_dial_number
+        Ok(Dial {
+            num_positions,
+            dial_number: start_position.rem_euclid(num_positions),
+        })
     }
 
-    fn rotate_right(&mut self, num_clicks: i32) {
-        let actual_num_clicks = Dial::actual_num_clicks(num_clicks);
+    // the clicks of a rotation visit every number between the start (exclusive) and the end (inclusive) without
+    // wrapping around, so the zero clicks are the multiples of num_positions in that range
+    fn rotate(&mut self, dial_rotation: &DialRotation) -> DialRotationResult {
+        let num_clicks = dial_rotation.num_clicks as i64;
 
-        let mut new_dial_number = self.dial_number + actual_num_clicks;
+        let (first_click, last_click) = match dial_rotation.direction {
+            DialDirection::LEFT => (self.dial_number - num_clicks, self.dial_number - 1),
+            DialDirection::RIGHT => (self.dial_number + 1, self.dial_number + num_clicks),
+        };
 
-        if new_dial_number > 99 {
-            new_dial_number = new_dial_number - 100
-        }
+        let num_zero_clicks = last_click.div_euclid(self.num_positions)
+            - (first_click - 1).div_euclid(self.num_positions);
 
-        self.dial_number = new_dial_number
-    }
+        let end_click = match dial_rotation.direction {
+            DialDirection::LEFT => first_click,
+            DialDirection::RIGHT => last_click,
+        };
 
-    fn rotate(&mut self, dial_rotation: &DialRotation) {
-        match dial_rotation.direction {
-            DialDirection::LEFT => self.rotate_left(dial_rotation.num_clicks),
-            DialDirection::RIGHT => self.rotate_right(dial_rotation.num_clicks),
+        self.dial_number = end_click.rem_euclid(self.num_positions);
+
+        DialRotationResult {
+            num_zero_clicks,
+            is_on_zero: self.dial_number == 0,
         }
     }
 }
 
 static DIAL_REGEX: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"(?P<direction>[RL])(?P<num_clicks>\d+)").unwrap());
+    LazyLock::new(|| Regex::new(r"^(?P<direction>[RL])(?P<num_clicks>[0-9]+)$").unwrap());
 
 #[aoc_generator(day1)]
-fn parse(input: &str) -> Vec<DialRotation> {
+fn parse(input: &str) -> Result<Vec<DialRotation>, InputError> {
     input
         .lines()
-        .map(|l| {
-            let captures = DIAL_REGEX.captures(l).unwrap();
+        .enumerate()
+        .map(|(line_i, l)| {
+            let captures = DIAL_REGEX
+                .captures(l.trim())
+                .ok_or_else(|| InputError::at_line(line_i, "expected a rotation like L68"))?;
             let direction = {
                 let d_str = captures.name("direction").unwrap().as_str();
                 let d: DialDirection;
@@ -89,89 +117,225 @@ fn parse(input: &str) -> Vec<DialRotation> {
 
                 d
             };
-            let num_clicks = captures
-                .name("num_clicks")
-                .unwrap()
-                .as_str()
-                .parse::<i32>()
-                .unwrap();
-
-            DialRotation {
+            let num_clicks = parse_number(captures.name("num_clicks").unwrap().as_str(), line_i)?;
+
+            Ok(DialRotation {
                 direction,
                 num_clicks,
-            }
+            })
         })
         .collect()
 }
 
-#[aoc(day1, part1)]
-fn part1(dial_rotations: &Vec<DialRotation>) -> i16 {
-    let mut num_zeros = 0;
-    let mut dial = Dial::new();
-
-    dial_rotations.iter().for_each(|rotation| {
-        dial.rotate(rotation);
+fn count_zero_landings(
+    mut dial: Dial,
+    dial_rotations: &[DialRotation],
+) -> Result<Answer, Overflow> {
+    let mut num_zeros: i64 = 0;
 
-        if dial.dial_number == 0 {
-            num_zeros += 1;
+    for rotation in dial_rotations {
+        if dial.rotate(rotation).is_on_zero {
+            num_zeros = checked_add(num_zeros, 1)?;
         }
 
-        println!(
-            "rotation: {:?}, num_clicks: {}, final_dial: {}",
-            rotation.direction, rotation.num_clicks, dial.dial_number
+        tracing::debug!(
+            direction = ?rotation.direction,
+            num_clicks = rotation.num_clicks,
+            final_dial = dial.dial_number,
+            "rotation"
         );
-    });
+    }
+
+    Ok(num_zeros.into())
+}
+
+fn count_zero_clicks(mut dial: Dial, dial_rotations: &[DialRotation]) -> Result<Answer, Overflow> {
+    let mut num_zeros: i64 = 0;
+
+    for rotation in dial_rotations {
+        num_zeros = checked_add(num_zeros, dial.rotate(rotation).num_zero_clicks)?;
+    }
 
-    return num_zeros;
+    Ok(num_zeros.into())
+}
+
+#[aoc(day1, part1)]
+fn part1(dial_rotations: &Vec<DialRotation>) -> Result<Answer, Overflow> {
+    count_zero_landings(Dial::new(DialOptions::default()).unwrap(), dial_rotations)
 }
 
 #[aoc(day1, part2)]
-fn part2(dial_rotations: &Vec<DialRotation>) -> i32 {
-    let mut num_zeros: i32 = 0;
-    let mut dial = Dial::new();
-
-    dial_rotations.iter().for_each(|rotation| {
-        let dial_number_before = dial.dial_number;
-        dial.rotate(rotation);
-        let dial_number_after = dial.dial_number;
-
-        num_zeros += Dial::number_full_rotations(rotation.num_clicks);
-
-        if dial_number_before != dial_number_after && dial_number_before != 0 {
-            match rotation.direction {
-                // 1 -> 99
-                // 1 -> 0
-                // 0 -> 99
-                DialDirection::LEFT => {
-                    if dial_number_before < dial_number_after || dial_number_after == 0 {
-                        num_zeros += 1;
-                    }
-                }
-                // 99 -> 1
-                // 99 -> 0
-                DialDirection::RIGHT => {
-                    if dial_number_after < dial_number_before {
-                        num_zeros += 1;
-                    }
-                }
+fn part2(dial_rotations: &Vec<DialRotation>) -> Result<Answer, Overflow> {
+    count_zero_clicks(Dial::new(DialOptions::default()).unwrap(), dial_rotations)
+}
+
+/// Both parts' answers for a dial of any size and start: how many rotations end on 0, and how many clicks do
+pub fn count_zeros(input: &str, options: DialOptions) -> Result<(Answer, Answer), String> {
+    let dial_rotations = parse(input).map_err(|e| e.to_string())?;
+    let dial = || Dial::new(options);
+
+    Ok((
+        count_zero_landings(dial()?, &dial_rotations).map_err(|e| e.to_string())?,
+        count_zero_clicks(dial()?, &dial_rotations).map_err(|e| e.to_string())?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const EXAMPLE: &str = "L68
+L30
+R48
+L5
+R60
+L55
+L1
+L99
+R14
+L82";
+
+    // turns the dial one click at a time
+    fn simulate(
+        num_positions: i64,
+        start_position: i64,
+        dial_rotation: &DialRotation,
+    ) -> (i64, i64) {
+        let mut dial_number = start_position;
+        let mut num_zero_clicks = 0;
+
+        for _ in 0..dial_rotation.num_clicks {
+            dial_number = match dial_rotation.direction {
+                DialDirection::LEFT => (dial_number + num_positions - 1) % num_positions,
+                DialDirection::RIGHT => (dial_number + 1) % num_positions,
+            };
+
+            if dial_number == 0 {
+                num_zero_clicks += 1;
             }
         }
-    });
 
-    return num_zeros;
-}
+        (dial_number, num_zero_clicks)
+    }
+
+    fn dial_rotation() -> impl Strategy<Value = DialRotation> {
+        (any::<bool>(), 0..1_000i32).prop_map(|(is_left, num_clicks)| DialRotation {
+            direction: if is_left {
+                DialDirection::LEFT
+            } else {
+                DialDirection::RIGHT
+            },
+            num_clicks,
+        })
+    }
 
-// #[cfg(test)]
-// mod tests {
-//     use super::*;
+    #[test]
+    fn part1_example() {
+        assert_eq!(part1(&parse(EXAMPLE).unwrap()), Ok(Answer::from(3)));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(part2(&parse(EXAMPLE).unwrap()), Ok(Answer::from(6)));
+    }
 
-//     #[test]
-//     fn part1_example() {
-//         assert_eq!(part1(&parse("<EXAMPLE>")), "<RESULT>");
-//     }
+    #[test]
+    fn part1_past_i16() {
+        let input = format!("L50{}", "\nR100".repeat(40_000));
+
+        assert_eq!(part1(&parse(&input).unwrap()), Ok(Answer::from(40_001)));
+    }
 
-//     #[test]
-//     fn part2_example() {
-//         assert_eq!(part2(&parse("<EXAMPLE>")), "<RESULT>");
-//     }
-// }
+    #[test]
+    fn parse_rejects_malformed_rotations() {
+        assert_eq!(
+            parse("L68\nX30").unwrap_err().to_string(),
+            "line 2: expected a rotation like L68"
+        );
+        assert!(parse("R99999999999").is_err());
+    }
+
+    #[test]
+    fn rotate_counts_zero_clicks() {
+        let mut dial = Dial::new(DialOptions {
+            num_positions: 10,
+            start_position: 0,
+        })
+        .unwrap();
+
+        let result = dial.rotate(&DialRotation {
+            direction: DialDirection::LEFT,
+            num_clicks: 10,
+        });
+        assert_eq!((dial.dial_number, result.num_zero_clicks), (0, 1));
+        assert!(result.is_on_zero);
+
+        let result = dial.rotate(&DialRotation {
+            direction: DialDirection::RIGHT,
+            num_clicks: 25,
+        });
+        assert_eq!((dial.dial_number, result.num_zero_clicks), (5, 2));
+        assert!(!result.is_on_zero);
+
+        let result = dial.rotate(&DialRotation {
+            direction: DialDirection::LEFT,
+            num_clicks: 0,
+        });
+        assert_eq!((dial.dial_number, result.num_zero_clicks), (5, 0));
+    }
+
+    #[test]
+    fn count_zeros_with_options() {
+        assert_eq!(
+            count_zeros(EXAMPLE, DialOptions::default()),
+            Ok((Answer::from(3), Answer::from(6)))
+        );
+        assert_eq!(
+            count_zeros(
+                "R5\nL15",
+                DialOptions {
+                    num_positions: 10,
+                    start_position: 0
+                }
+            ),
+            Ok((Answer::from(1), Answer::from(2)))
+        );
+        assert_eq!(
+            count_zeros(
+                EXAMPLE,
+                DialOptions {
+                    num_positions: 0,
+                    start_position: 0
+                }
+            ),
+            Err(String::from("a dial needs at least one position, not 0"))
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn rotate_matches_click_simulation(
+            num_positions in 1..150i64,
+            start_position in 0..150i64,
+            dial_rotations in prop::collection::vec(dial_rotation(), 0..20),
+        ) {
+            let mut dial = Dial::new(DialOptions { num_positions, start_position }).unwrap();
+            let mut dial_number = start_position % num_positions;
+
+            for dial_rotation in &dial_rotations {
+                let (expected_dial_number, expected_num_zero_clicks) =
+                    simulate(num_positions, dial_number, dial_rotation);
+
+                let result = dial.rotate(dial_rotation);
+
+                prop_assert_eq!(dial.dial_number, expected_dial_number);
+                prop_assert_eq!(result.num_zero_clicks, expected_num_zero_clicks);
+                prop_assert_eq!(result.is_on_zero, expected_dial_number == 0);
+
+                dial_number = expected_dial_number;
+            }
+        }
+    }
+}
@@ -1,3 +1,30 @@
-fn main() {
-    println!("Hello, world!");
+use aoc_tools::aoc_main;
+
+extern crate aoc_2025;
+
+aoc_main! { lib = aoc_2025 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use aoc_tools::{aoc_solutions, fuzz};
+
+    // what the fuzz target in fuzz/ does, on a small deterministic scale so that it runs on stable with every test run
+    #[test]
+    fn test_solutions_do_not_panic_on_mutated_fixtures() {
+        let solutions = aoc_solutions! { lib = aoc_2025 };
+
+        for (seed_i, (name, seed)) in fuzz::seed_corpus(Path::new("fixtures"))
+            .unwrap()
+            .iter()
+            .enumerate()
+        {
+            let inputs = fuzz::mutations(seed, 300, seed_i as u64);
+
+            if let Some(panic) = fuzz::find_panic(&solutions, &inputs) {
+                panic!("{name}: {panic}");
+            }
+        }
+    }
 }
@@ -0,0 +1,173 @@
+use std::{
+    env, fs,
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+use aoc_runner_internal::DayParts;
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+
+fn parse_lib(input: TokenStream) -> Option<String> {
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+
+    match tokens.as_slice() {
+        [
+            TokenTree::Ident(key),
+            TokenTree::Punct(eq),
+            TokenTree::Ident(lib),
+        ] if key.to_string() == "lib" && eq.as_char() == '=' => Some(lib.to_string()),
+        [TokenTree::Ident(lib)] => Some(lib.to_string()),
+        _ => None,
+    }
+}
+
+// `aoc_lib!` writes the registry into the directory the library is built from, so crates nested inside a year's
+// directory, like fuzz/, find it in a parent directory
+fn load_day_parts() -> Option<DayParts> {
+    env::current_dir().ok()?.ancestors().find_map(|dir| {
+        let file = fs::File::open(dir.join("target/aoc/completed.json")).ok()?;
+
+        serde_json::from_reader(file).ok()
+    })
+}
+
+// `aoc_lib!` writes every `#[aoc]` solution of the library to target/aoc/completed.json; this turns them into a
+// `Vec<aoc_tools::runner::Solution>` pointing at the factories `aoc_lib!` generated for them
+fn solutions(lib: &str) -> String {
+    let day_parts = load_day_parts()
+        .expect("failed to read target/aoc/completed.json; is `aoc_lib!` at the end of lib.rs?");
+
+    let solutions: String = day_parts
+        .iter()
+        .map(|day_part| {
+            let (day, part) = (day_part.day.0, day_part.part.0);
+
+            let (name, factory_trait, factory) = match &day_part.name {
+                Some(name) => (
+                    format!("Some({name:?})"),
+                    format!("Day{day}Part{part}{}", name.to_uppercase()),
+                    format!("day{day}_part{part}_{}", name.to_lowercase()),
+                ),
+                None => (
+                    String::from("None"),
+                    format!("Day{day}Part{part}"),
+                    format!("day{day}_part{part}"),
+                ),
+            };
+
+            format!(
+                "aoc_tools::runner::Solution {{
+                    day: {day},
+                    part: {part},
+                    name: {name},
+                    factory: <{lib}::Factory as {lib}::{factory_trait}>::{factory},
+                }},"
+            )
+        })
+        .collect();
+
+    format!("vec![{solutions}]")
+}
+
+/// Every solution registered in a library crate with `aoc_lib!`: `aoc_solutions!(advent_of_code_2024)`
+#[proc_macro]
+pub fn aoc_solutions(input: TokenStream) -> TokenStream {
+    let lib = parse_lib(input).expect("expected `aoc_solutions!(<library crate>)`");
+
+    solutions(&lib).parse().unwrap()
+}
+
+/// Drop-in replacement for aoc-runner's `aoc_main! { lib = ... }` that runs every solution with the default
+/// `aoc_tools::runner::Runner`, allocating through `aoc_tools::alloc::CountingAllocator` for `--memory`
+#[proc_macro]
+pub fn aoc_main(input: TokenStream) -> TokenStream {
+    let lib = parse_lib(input).expect("expected `aoc_main! { lib = <library crate> }`");
+
+    format!(
+        "#[global_allocator]
+        static AOC_TOOLS_ALLOCATOR: aoc_tools::alloc::CountingAllocator = aoc_tools::alloc::CountingAllocator;
+
+        fn main() -> std::process::ExitCode {{
+            aoc_tools::runner::Runner::new({lib}::YEAR, {}).main()
+        }}",
+        solutions(&lib)
+    )
+    .parse()
+    .unwrap()
+}
+
+// `#[cached]` or `#[cached(version = 2)]`
+fn parse_version(args: TokenStream) -> Option<u64> {
+    let tokens: Vec<TokenTree> = args.into_iter().collect();
+
+    match tokens.as_slice() {
+        [] => Some(0),
+        [
+            TokenTree::Ident(key),
+            TokenTree::Punct(eq),
+            TokenTree::Literal(version),
+        ] if key.to_string() == "version" && eq.as_char() == '=' => {
+            version.to_string().parse().ok()
+        }
+        _ => None,
+    }
+}
+
+/// Caches what a generator returns, keyed by a hash of its input and of its source, when the runner runs with
+/// `--cache`. It goes on a `fn(input: &str) -> Result<T, E>` whose `T` is serde's `Serialize` and `Deserialize`,
+/// below any `#[aoc_generator]`. Changing the function invalidates its entries; bump `#[cached(version = N)]` when
+/// something it calls changes instead.
+#[proc_macro_attribute]
+pub fn cached(args: TokenStream, item: TokenStream) -> TokenStream {
+    let version =
+        parse_version(args).expect("expected `#[cached]` or `#[cached(version = <integer>)]`");
+
+    let mut hasher = DefaultHasher::new();
+    (version, item.to_string()).hash(&mut hasher);
+    let version = hasher.finish();
+
+    let mut tokens: Vec<TokenTree> = item.into_iter().collect();
+
+    let name = tokens
+        .iter()
+        .skip_while(|token| !matches!(token, TokenTree::Ident(ident) if ident.to_string() == "fn"))
+        .nth(1)
+        .map(ToString::to_string)
+        .expect("`#[cached]` goes on a function");
+
+    // the first parameter, skipping a `mut`
+    let input = tokens
+        .iter()
+        .find_map(|token| match token {
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis => {
+                group.stream().into_iter().find_map(|token| match token {
+                    TokenTree::Ident(ident) if ident.to_string() != "mut" => {
+                        Some(ident.to_string())
+                    }
+                    _ => None,
+                })
+            }
+            _ => None,
+        })
+        .expect("a `#[cached]` function takes its input as its first parameter");
+
+    let body = match tokens.pop() {
+        Some(TokenTree::Group(body)) if body.delimiter() == Delimiter::Brace => body,
+        _ => panic!("`#[cached]` goes on a function with a body"),
+    };
+
+    let signature: TokenStream = tokens.into_iter().collect();
+
+    format!(
+        "{signature} {{
+            aoc_tools::cache::cached(
+                env!(\"CARGO_MANIFEST_DIR\"),
+                concat!(module_path!(), \"::{name}\"),
+                {version},
+                {input},
+                move || {body},
+            )
+        }}"
+    )
+    .parse()
+    .unwrap()
+}
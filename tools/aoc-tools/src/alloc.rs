@@ -0,0 +1,242 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    fmt,
+    hint::black_box,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use serde::{Deserialize, Serialize};
+
+static IS_COUNTING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Copy)]
+struct Counters {
+    allocations: u64,
+    allocated_bytes: u64,
+    // memory allocated on another thread can be freed on this one, so this can go negative
+    live_bytes: i64,
+    peak_live_bytes: i64,
+}
+
+// counted per thread so solutions measured on different threads do not see each other's allocations; allocations of
+// threads a solution spawns itself are not counted
+thread_local! {
+    static COUNTERS: Cell<Counters> = const {
+        Cell::new(Counters {
+            allocations: 0,
+            allocated_bytes: 0,
+            live_bytes: 0,
+            peak_live_bytes: 0,
+        })
+    };
+}
+
+fn record(allocated_bytes: usize, freed_bytes: usize) {
+    if !IS_COUNTING.load(Ordering::Relaxed) {
+        return;
+    }
+
+    // the counters are gone while a thread is being torn down
+    let _ = COUNTERS.try_with(|counters| {
+        let mut c = counters.get();
+
+        if allocated_bytes > 0 {
+            c.allocations += 1;
+            c.allocated_bytes += allocated_bytes as u64;
+        }
+
+        c.live_bytes += allocated_bytes as i64 - freed_bytes as i64;
+        c.peak_live_bytes = c.peak_live_bytes.max(c.live_bytes);
+
+        counters.set(c);
+    });
+}
+
+/// The global allocator of a runner that can measure memory; `aoc_main!` installs it. It only starts counting once
+/// `enable` is called, so without `--memory` the cost is one relaxed load per allocation.
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: aoc_tools::alloc::CountingAllocator = aoc_tools::alloc::CountingAllocator;
+/// ```
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+
+        record(0, layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+
+        if !new_ptr.is_null() {
+            record(new_size, layout.size());
+        }
+
+        new_ptr
+    }
+}
+
+/// Starts counting; fails when `CountingAllocator` is not the global allocator, since nothing would be counted
+pub fn enable() -> Result<(), String> {
+    IS_COUNTING.store(true, Ordering::Relaxed);
+
+    let (_, stats) = measure(|| black_box(Box::new(0u8)));
+
+    if stats.is_some_and(|stats| stats.allocations > 0) {
+        Ok(())
+    } else {
+        IS_COUNTING.store(false, Ordering::Relaxed);
+
+        Err(String::from(
+            "--memory needs aoc_tools::alloc::CountingAllocator as the #[global_allocator] (aoc_main! installs it)",
+        ))
+    }
+}
+
+pub fn is_enabled() -> bool {
+    IS_COUNTING.load(Ordering::Relaxed)
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub allocated_bytes: u64,
+    /// The most memory live at once, on top of what was live when the measurement started
+    pub peak_live_bytes: u64,
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit_i = 0;
+
+    while value >= 1024.0 && unit_i + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit_i += 1;
+    }
+
+    if unit_i == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit_i])
+    }
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocs, {} allocated, {} peak",
+            self.allocations,
+            format_bytes(self.allocated_bytes),
+            format_bytes(self.peak_live_bytes)
+        )
+    }
+}
+
+/// Runs `f` and counts what it allocates on this thread; `None` unless counting is enabled
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !is_enabled() {
+        return (f(), None);
+    }
+
+    let start = COUNTERS.with(|counters| {
+        let mut c = counters.get();
+        c.peak_live_bytes = c.live_bytes;
+        counters.set(c);
+        c
+    });
+
+    let result = f();
+
+    let end = COUNTERS.with(Cell::get);
+
+    let stats = AllocStats {
+        allocations: end.allocations - start.allocations,
+        allocated_bytes: end.allocated_bytes - start.allocated_bytes,
+        peak_live_bytes: (end.peak_live_bytes - start.live_bytes).max(0) as u64,
+    };
+
+    (result, Some(stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn test_measure() {
+        enable().unwrap();
+
+        let (sum, stats) = measure(|| {
+            let mut values: Vec<u64> = Vec::with_capacity(1000);
+            values.extend(0..1000);
+
+            // 8000 bytes live at once, then freed before the end
+            let sum: u64 = black_box(&values).iter().sum();
+            drop(values);
+
+            let small: Vec<u8> = black_box(vec![1; 10]);
+            sum + small.len() as u64
+        });
+
+        let stats = stats.unwrap();
+
+        assert_eq!(sum, 499_500 + 10);
+        assert_eq!(stats.allocations, 2);
+        assert_eq!(stats.allocated_bytes, 8010);
+        assert_eq!(stats.peak_live_bytes, 8000);
+    }
+
+    #[test]
+    fn test_measure_counts_only_this_thread() {
+        enable().unwrap();
+
+        let (_, stats) = measure(|| {
+            std::thread::scope(|scope| {
+                scope.spawn(|| black_box(vec![0u8; 4096]).len());
+            });
+        });
+
+        assert!(stats.unwrap().allocated_bytes < 4096);
+    }
+
+    #[test]
+    fn test_format() {
+        let stats = AllocStats {
+            allocations: 3,
+            allocated_bytes: 1536,
+            peak_live_bytes: 512,
+        };
+
+        assert_eq!(stats.to_string(), "3 allocs, 1.5 KiB allocated, 512 B peak");
+    }
+}
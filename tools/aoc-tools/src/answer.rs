@@ -0,0 +1,226 @@
+use std::{any, cell::Cell, error::Error, fmt};
+
+pub use num_bigint::BigInt;
+use num_traits::{CheckedAdd, CheckedMul, Zero};
+
+use crate::input::InputError;
+
+/// What a part answers, whatever it was computed in: an integer of up to 128 bits, a big integer, or text such as a
+/// password or a list. Parts return it so the runner prints every answer the same way.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Int(i128),
+    Big(BigInt),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(answer) => write!(f, "{answer}"),
+            Answer::Big(answer) => write!(f, "{answer}"),
+            Answer::Text(answer) => write!(f, "{answer}"),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(answer: $int) -> Answer {
+                    Answer::Int(answer as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl From<u128> for Answer {
+    fn from(answer: u128) -> Answer {
+        i128::try_from(answer)
+            .map(Answer::Int)
+            .unwrap_or_else(|_| Answer::Big(answer.into()))
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(answer: BigInt) -> Answer {
+        Answer::Big(answer)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(answer: String) -> Answer {
+        Answer::Text(answer)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(answer: &str) -> Answer {
+        Answer::Text(answer.to_string())
+    }
+}
+
+thread_local! {
+    // the day and part the runner is running on this thread, for overflow errors to name
+    static CURRENT_SOLUTION: Cell<Option<(u8, u8)>> = const { Cell::new(None) };
+}
+
+/// Runs `f` as `day` and `part`, which overflow errors raised meanwhile on this thread then name
+pub(crate) fn in_solution<T>(day: u8, part: u8, f: impl FnOnce() -> T) -> T {
+    // restores the previous solution even when `f` panics
+    struct Exit(Option<(u8, u8)>);
+
+    impl Drop for Exit {
+        fn drop(&mut self) {
+            CURRENT_SOLUTION.set(self.0);
+        }
+    }
+
+    let _exit = Exit(CURRENT_SOLUTION.replace(Some((day, part))));
+
+    f()
+}
+
+/// An answer that grew past the integer type it was accumulated in
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Overflow {
+    /// The day and part that overflowed; `None` outside the runner, e.g. in unit tests
+    pub solution: Option<(u8, u8)>,
+    pub type_name: &'static str,
+    /// e.g. "adding 97 to 65500"
+    pub operation: String,
+}
+
+impl Overflow {
+    fn new<T>(operation: String) -> Overflow {
+        Overflow {
+            solution: CURRENT_SOLUTION.get(),
+            type_name: any::type_name::<T>(),
+            operation,
+        }
+    }
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some((day, part)) = self.solution {
+            write!(f, "Day {day} - Part {part}: ")?;
+        }
+
+        write!(f, "{} overflowed {}", self.type_name, self.operation)
+    }
+}
+
+impl Error for Overflow {}
+
+/// Why a part has no answer: its input is malformed, or the answer outgrew the type it was accumulated in. Parts that
+/// use both `InputError` and the checked arithmetic below return this, so that `?` works on either.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolutionError {
+    Input(InputError),
+    Overflow(Overflow),
+}
+
+impl From<InputError> for SolutionError {
+    fn from(e: InputError) -> SolutionError {
+        SolutionError::Input(e)
+    }
+}
+
+impl From<Overflow> for SolutionError {
+    fn from(overflow: Overflow) -> SolutionError {
+        SolutionError::Overflow(overflow)
+    }
+}
+
+impl fmt::Display for SolutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolutionError::Input(e) => write!(f, "{e}"),
+            SolutionError::Overflow(overflow) => write!(f, "{overflow}"),
+        }
+    }
+}
+
+impl Error for SolutionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SolutionError::Input(_) => None,
+            SolutionError::Overflow(overflow) => Some(overflow),
+        }
+    }
+}
+
+/// `a + b`, or an error naming the running day and part if it does not fit in `T`. Release builds would wrap around
+/// silently instead.
+pub fn checked_add<T: CheckedAdd + fmt::Display>(a: T, b: T) -> Result<T, Overflow> {
+    a.checked_add(&b)
+        .ok_or_else(|| Overflow::new::<T>(format!("adding {b} to {a}")))
+}
+
+/// `a * b`, or an error naming the running day and part if it does not fit in `T`
+pub fn checked_mul<T: CheckedMul + fmt::Display>(a: T, b: T) -> Result<T, Overflow> {
+    a.checked_mul(&b)
+        .ok_or_else(|| Overflow::new::<T>(format!("multiplying {a} by {b}")))
+}
+
+/// The sum of `terms`, checked like `checked_add`
+pub fn checked_sum<T: CheckedAdd + Zero + fmt::Display>(
+    terms: impl IntoIterator<Item = T>,
+) -> Result<T, Overflow> {
+    terms.into_iter().try_fold(T::zero(), checked_add)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(-3_i16).to_string(), "-3");
+        assert_eq!(Answer::from(u64::MAX), Answer::Int(u64::MAX as i128));
+        assert_eq!(
+            Answer::from(u128::MAX).to_string(),
+            "340282366920938463463374607431768211455"
+        );
+        assert!(matches!(Answer::from(u128::MAX), Answer::Big(_)));
+        assert_eq!(Answer::from("abc,def").to_string(), "abc,def");
+    }
+
+    #[test]
+    fn test_checked() {
+        assert_eq!(checked_sum([1_u8, 2, 3]), Ok(6));
+        assert_eq!(checked_mul(16_u8, 15), Ok(240));
+
+        assert_eq!(
+            checked_sum([200_u8, 50, 6]).unwrap_err().to_string(),
+            "u8 overflowed adding 6 to 250"
+        );
+
+        let overflow = in_solution(5, 2, || checked_mul(16_u8, 16)).unwrap_err();
+
+        assert_eq!(
+            overflow.to_string(),
+            "Day 5 - Part 2: u8 overflowed multiplying 16 by 16"
+        );
+        assert_eq!(checked_add(i8::MIN, -1).unwrap_err().solution, None);
+    }
+
+    #[test]
+    fn test_solution_error() {
+        let overflow = checked_add(u8::MAX, 1).unwrap_err();
+        let e = SolutionError::from(overflow.clone());
+
+        assert_eq!(e.to_string(), "u8 overflowed adding 1 to 255");
+        assert_eq!(e.source().unwrap().downcast_ref(), Some(&overflow));
+
+        let e = SolutionError::from(InputError::at_line(2, "no dial"));
+
+        assert_eq!(e.to_string(), "line 3: no dial");
+        assert!(e.source().is_none());
+    }
+}
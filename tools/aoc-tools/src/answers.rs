@@ -0,0 +1,114 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::fixture::{parse_answer, to_answer_value};
+
+/// The answers the website accepted for a day's puzzle input, stored as `answers/day<N>.toml`:
+///
+/// ```toml
+/// part1 = 143
+/// part2 = 123
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AcceptedAnswers {
+    pub answers: HashMap<u8, String>,
+}
+
+#[derive(Default, Deserialize, Serialize)]
+struct AnswersFile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1: Option<toml::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2: Option<toml::Value>,
+}
+
+pub const ANSWERS_DIR: &str = "answers";
+
+pub fn day_path(answers_dir: &Path, day: u8) -> PathBuf {
+    answers_dir.join(format!("day{day}.toml"))
+}
+
+impl AcceptedAnswers {
+    pub fn get(&self, part: u8) -> Option<&str> {
+        self.answers.get(&part).map(String::as_str)
+    }
+
+    pub fn parse(contents: &str) -> Result<AcceptedAnswers, String> {
+        let file: AnswersFile = toml::from_str(contents).map_err(|e| e.to_string())?;
+
+        let mut answers = HashMap::new();
+
+        for (part, answer) in [(1, file.part1), (2, file.part2)] {
+            if let Some(answer) = answer {
+                answers.insert(part, parse_answer(answer)?);
+            }
+        }
+
+        Ok(AcceptedAnswers { answers })
+    }
+
+    pub fn to_toml(&self) -> String {
+        let file = AnswersFile {
+            part1: self.get(1).map(to_answer_value),
+            part2: self.get(2).map(to_answer_value),
+        };
+
+        toml::to_string(&file).unwrap()
+    }
+
+    /// The accepted answers of a day in `answers_dir`; none when the day has no file
+    pub fn load(answers_dir: &Path, day: u8) -> Result<AcceptedAnswers, String> {
+        let path = day_path(answers_dir, day);
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => AcceptedAnswers::parse(&contents)
+                .map_err(|e| format!("invalid answers {}: {e}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(AcceptedAnswers::default()),
+            Err(e) => Err(format!("failed to read {}: {e}", path.display())),
+        }
+    }
+
+    /// Records `answer` for `part` of `day`, keeping the other part's answer
+    pub fn accept(answers_dir: &Path, day: u8, part: u8, answer: &str) -> Result<(), String> {
+        let mut answers = AcceptedAnswers::load(answers_dir, day)?;
+        answers.answers.insert(part, answer.to_string());
+
+        let path = day_path(answers_dir, day);
+
+        fs::create_dir_all(answers_dir)
+            .and_then(|()| fs::write(&path, answers.to_toml()))
+            .map_err(|e| format!("failed to write {}: {e}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accept_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+
+        assert_eq!(
+            AcceptedAnswers::load(dir.path(), 5),
+            Ok(AcceptedAnswers::default())
+        );
+
+        AcceptedAnswers::accept(dir.path(), 5, 2, "123").unwrap();
+        AcceptedAnswers::accept(dir.path(), 5, 1, "abc").unwrap();
+
+        let answers = AcceptedAnswers::load(dir.path(), 5).unwrap();
+
+        assert_eq!(answers.get(1), Some("abc"));
+        assert_eq!(answers.get(2), Some("123"));
+        assert_eq!(
+            fs::read_to_string(day_path(dir.path(), 5)).unwrap(),
+            "part1 = \"abc\"\npart2 = 123\n"
+        );
+    }
+}
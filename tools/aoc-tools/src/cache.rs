@@ -0,0 +1,330 @@
+use std::{
+    any, fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    io,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Where `#[cached]` generators store their output, inside the year's crate directory; `cargo clean` removes it too
+pub const CACHE_DIR: &str = "target/aoc-cache";
+
+static IS_ENABLED: AtomicBool = AtomicBool::new(false);
+
+// tells apart the temporary files of writers in this process
+static NUM_WRITES: AtomicU64 = AtomicU64::new(0);
+
+/// Makes `#[cached]` generators use the cache; until then, e.g. in unit tests, they always run
+pub fn enable() {
+    IS_ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    IS_ENABLED.load(Ordering::Relaxed)
+}
+
+fn hash(value: impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+// "advent_of_code_2024::day6::parse" is stored as "advent_of_code_2024-day6-parse-..."
+fn file_prefix(generator: &str) -> String {
+    format!("{}-", generator.replace("::", "-"))
+}
+
+/// The file `generator` stores its output for `input` in. `version` changes whenever the generator's source does,
+/// and the output type is part of the key as well, so a stale entry is never found rather than misread.
+fn entry_path<T>(cache_dir: &Path, generator: &str, version: u64, input: &[u8]) -> PathBuf {
+    let version = hash((version, any::type_name::<T>()));
+
+    cache_dir.join(format!(
+        "{}{version:016x}-{:016x}.json",
+        file_prefix(generator),
+        hash(input)
+    ))
+}
+
+fn read<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    let contents = fs::read(path).ok()?;
+
+    serde_json::from_slice(&contents).ok()
+}
+
+fn write<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    let cache_dir = path.parent().unwrap();
+    fs::create_dir_all(cache_dir).map_err(|e| e.to_string())?;
+
+    let contents = serde_json::to_vec(value).map_err(|e| e.to_string())?;
+
+    // written aside and renamed, so that parts running in parallel never read a half-written entry
+    let temp_path = path.with_extension(format!(
+        "{}-{}.tmp",
+        process::id(),
+        NUM_WRITES.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    fs::write(&temp_path, contents)
+        .and_then(|()| fs::rename(&temp_path, path))
+        .map_err(|e| e.to_string())
+}
+
+// entries of an older version of the generator are never read again
+fn remove_stale(path: &Path, generator: &str) {
+    let (Some(cache_dir), Some(file_name)) = (path.parent(), path.file_name()) else {
+        return;
+    };
+    let Ok(entries) = fs::read_dir(cache_dir) else {
+        return;
+    };
+
+    let prefix = file_prefix(generator);
+    let version_prefix = &file_name.to_string_lossy()[..prefix.len() + 16];
+
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+
+        if name.starts_with(&prefix) && name.ends_with(".json") && !name.starts_with(version_prefix)
+        {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+}
+
+/// What `#[cached]` turns a generator's body into: its output for `input` from the cache in `crate_dir` if it is
+/// enabled and has one, and otherwise `generate()`, stored when it succeeds. Failing to use the cache is traced but
+/// never fails the generator.
+#[doc(hidden)]
+pub fn cached<T, E>(
+    crate_dir: &str,
+    generator: &str,
+    version: u64,
+    input: impl AsRef<[u8]>,
+    generate: impl FnOnce() -> Result<T, E>,
+) -> Result<T, E>
+where
+    T: Serialize + DeserializeOwned,
+{
+    if !is_enabled() {
+        return generate();
+    }
+
+    let path = entry_path::<T>(
+        &Path::new(crate_dir).join(CACHE_DIR),
+        generator,
+        version,
+        input.as_ref(),
+    );
+
+    if let Some(output) = read(&path) {
+        tracing::debug!(generator, path = %path.display(), "cache hit");
+        return Ok(output);
+    }
+
+    let output = generate()?;
+
+    match write(&path, &output) {
+        Ok(()) => {
+            tracing::debug!(generator, path = %path.display(), "cache miss, stored");
+            remove_stale(&path, generator);
+        }
+        Err(e) => tracing::warn!(generator, path = %path.display(), "failed to cache: {e}"),
+    }
+
+    Ok(output)
+}
+
+/// Removes every cached generator output in `cache_dir`, returning how many entries and bytes there were
+pub fn clear(cache_dir: &Path) -> Result<(usize, u64), String> {
+    let entries = match fs::read_dir(cache_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok((0, 0)),
+        Err(e) => return Err(format!("failed to read {}: {e}", cache_dir.display())),
+    };
+
+    let (mut num_entries, mut num_bytes) = (0, 0);
+
+    for entry in entries {
+        let entry = entry.map_err(|e| e.to_string())?;
+
+        num_entries += 1;
+        num_bytes += entry.metadata().map_or(0, |metadata| metadata.len());
+    }
+
+    fs::remove_dir_all(cache_dir)
+        .map_err(|e| format!("failed to remove {}: {e}", cache_dir.display()))?;
+
+    Ok((num_entries, num_bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    // the same day before and after editing only its part: the generator's tokens are the same
+    mod day_a {
+        #[crate::cached]
+        pub fn parse(input: &str) -> Result<Vec<u32>, String> {
+            input
+                .split(',')
+                .map(|n| n.parse().map_err(|_| n.to_string()))
+                .collect()
+        }
+
+        pub fn part1(input: &str) -> u32 {
+            parse(input).unwrap().iter().sum()
+        }
+    }
+
+    mod day_b {
+        #[crate::cached]
+        pub fn parse(input: &str) -> Result<Vec<u32>, String> {
+            input
+                .split(',')
+                .map(|n| n.parse().map_err(|_| n.to_string()))
+                .collect()
+        }
+
+        pub fn part1(input: &str) -> u32 {
+            parse(input).unwrap().iter().product()
+        }
+    }
+
+    // the generator itself edited
+    mod day_c {
+        #[crate::cached]
+        pub fn parse(input: &str) -> Result<Vec<u32>, String> {
+            input
+                .split(',')
+                .map(|n| n.trim().parse().map_err(|_| n.to_string()))
+                .collect()
+        }
+    }
+
+    // something the generator calls edited
+    mod day_d {
+        #[crate::cached(version = 2)]
+        pub fn parse(input: &str) -> Result<Vec<u32>, String> {
+            input
+                .split(',')
+                .map(|n| n.parse().map_err(|_| n.to_string()))
+                .collect()
+        }
+    }
+
+    // the version part of the name of the entry `generate` stores for `input`
+    fn entry_version(day: &str, input: &str, generate: impl FnOnce(&str)) -> String {
+        let cache_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(CACHE_DIR);
+        let prefix = file_prefix(&format!("{}::{day}::parse", module_path!()));
+
+        generate(input);
+
+        let entry_path = fs::read_dir(&cache_dir)
+            .unwrap()
+            .flatten()
+            .map(|entry| entry.path())
+            .find(|path| path.to_string_lossy().contains(&prefix))
+            .unwrap();
+        fs::remove_file(&entry_path).unwrap();
+
+        let file_name = entry_path
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .into_owned();
+        file_name[prefix.len()..prefix.len() + 16].to_string()
+    }
+
+    #[test]
+    fn test_cached_version() {
+        enable();
+
+        let input = "4,5,6";
+        let version = entry_version("day_a", input, |input| {
+            day_a::part1(input);
+        });
+
+        assert_eq!(
+            entry_version("day_b", input, |input| {
+                day_b::part1(input);
+            }),
+            version
+        );
+        assert_ne!(
+            entry_version("day_c", input, |input| {
+                day_c::parse(input).unwrap();
+            }),
+            version
+        );
+        assert_ne!(
+            entry_version("day_d", input, |input| {
+                day_d::parse(input).unwrap();
+            }),
+            version
+        );
+    }
+
+    #[test]
+    fn test_cached() {
+        let dir = tempfile::tempdir().unwrap();
+        let crate_dir = dir.path().to_str().unwrap();
+        let cache_dir = dir.path().join(CACHE_DIR);
+
+        let num_runs = Cell::new(0);
+        let generate = |input: &str, version: u64| {
+            cached(crate_dir, "day1::parse", version, input, || {
+                num_runs.set(num_runs.get() + 1);
+
+                input
+                    .split(',')
+                    .map(|n| n.parse::<u32>().map_err(|e| e.to_string()))
+                    .collect::<Result<Vec<u32>, String>>()
+            })
+        };
+
+        // not enabled yet: nothing is stored
+        assert_eq!(generate("1,2", 1), Ok(vec![1, 2]));
+        assert!(!cache_dir.exists());
+
+        enable();
+
+        assert_eq!(generate("1,2", 1), Ok(vec![1, 2]));
+        assert_eq!(generate("1,2", 1), Ok(vec![1, 2]));
+        assert_eq!(num_runs.get(), 2);
+
+        // another input is another entry, and errors are not stored
+        assert_eq!(generate("3", 1), Ok(vec![3]));
+        assert!(generate("x", 1).is_err());
+        assert!(generate("x", 1).is_err());
+        assert_eq!(num_runs.get(), 5);
+        assert_eq!(fs::read_dir(&cache_dir).unwrap().count(), 2);
+
+        // a new version of the generator replaces the entries of the old one
+        assert_eq!(generate("1,2", 2), Ok(vec![1, 2]));
+        assert_eq!(num_runs.get(), 6);
+        assert_eq!(fs::read_dir(&cache_dir).unwrap().count(), 1);
+
+        // an entry that no longer reads as the output type is generated again
+        let path = fs::read_dir(&cache_dir)
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path();
+        fs::write(&path, "{").unwrap();
+
+        assert_eq!(generate("1,2", 2), Ok(vec![1, 2]));
+        assert_eq!(num_runs.get(), 7);
+
+        assert_eq!(clear(&cache_dir).map(|(num_entries, _)| num_entries), Ok(1));
+        assert!(!cache_dir.exists());
+        assert_eq!(clear(&cache_dir), Ok((0, 0)));
+    }
+}
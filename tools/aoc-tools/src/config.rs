@@ -0,0 +1,354 @@
+use std::{
+    collections::BTreeMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::{answers::ANSWERS_DIR, input::Normalization, profile::PROFILES_DIR, runner::parse_day};
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+pub const INPUT_DIR: &str = "input";
+
+/// The configuration of a year's directory, read from its `aoc.toml` by the runner and every `aoc` command. Every
+/// setting is optional, and relative paths are relative to the year's directory:
+///
+/// ```toml
+/// year = 2024                               # for `aoc` commands run outside a directory named after the year
+/// input_dir = "input"                       # puzzle inputs are <input_dir>/<year>/day<N>.txt
+/// answers_dir = "answers"                   # accepted answers are <answers_dir>/day<N>.toml
+/// session_file = "~/.config/aoc/session"    # the session cookie; must only be readable by its owner
+/// server = "https://adventofcode.com"
+/// cache = true                              # as if the runner always got `--cache`
+///
+/// [days.6]
+/// input = "input/2024/day6-large.txt"       # instead of <input_dir>/<year>/day6.txt
+/// trim_trailing_blank_lines = false         # and the other `Normalization` steps
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    /// The year's directory, which relative paths are relative to
+    pub dir: PathBuf,
+    pub year: Option<u16>,
+    pub input_dir: PathBuf,
+    pub answers_dir: PathBuf,
+    pub session_file: Option<PathBuf>,
+    pub server: Option<String>,
+    pub cache: bool,
+    pub days: BTreeMap<u8, DayConfig>,
+}
+
+/// The settings of one day; the normalization steps override what the day registered with `Runner::normalization`
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DayConfig {
+    pub input: Option<PathBuf>,
+    pub strip_bom: Option<bool>,
+    pub crlf_to_lf: Option<bool>,
+    pub trim_trailing_blank_lines: Option<bool>,
+}
+
+impl DayConfig {
+    pub fn normalization(&self, normalization: Normalization) -> Normalization {
+        Normalization {
+            strip_bom: self.strip_bom.unwrap_or(normalization.strip_bom),
+            crlf_to_lf: self.crlf_to_lf.unwrap_or(normalization.crlf_to_lf),
+            trim_trailing_blank_lines: self
+                .trim_trailing_blank_lines
+                .unwrap_or(normalization.trim_trailing_blank_lines),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    year: Option<u16>,
+    input_dir: Option<PathBuf>,
+    answers_dir: Option<PathBuf>,
+    session_file: Option<PathBuf>,
+    server: Option<String>,
+    #[serde(default)]
+    cache: bool,
+    #[serde(default)]
+    days: BTreeMap<String, DayConfig>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config::new(PathBuf::new())
+    }
+}
+
+// `~/...` is the home directory, as in a shell
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+impl Config {
+    /// Every setting at its default, for the year's directory `dir`
+    pub fn new(dir: PathBuf) -> Config {
+        Config {
+            input_dir: dir.join(INPUT_DIR),
+            answers_dir: dir.join(ANSWERS_DIR),
+            dir,
+            year: None,
+            session_file: None,
+            server: None,
+            cache: false,
+            days: BTreeMap::new(),
+        }
+    }
+
+    pub fn parse(contents: &str, dir: &Path) -> Result<Config, String> {
+        let file: ConfigFile = toml::from_str(contents).map_err(|e| e.to_string())?;
+        let mut config = Config::new(dir.to_path_buf());
+
+        if let Some(year) = file.year {
+            if year < 2015 {
+                return Err(format!(
+                    "year {year} is before the first Advent of Code, 2015"
+                ));
+            }
+
+            config.year = Some(year);
+        }
+
+        if let Some(server) = file.server {
+            if !server.starts_with("http://") && !server.starts_with("https://") {
+                return Err(format!(
+                    "server {server:?} is not an http:// or https:// URL"
+                ));
+            }
+
+            config.server = Some(server);
+        }
+
+        config.cache = file.cache;
+
+        let resolve = |path: PathBuf| dir.join(expand_home(&path));
+
+        config.input_dir = file.input_dir.map_or(config.input_dir, resolve);
+        config.answers_dir = file.answers_dir.map_or(config.answers_dir, resolve);
+        config.session_file = file.session_file.map(resolve);
+
+        for (day, mut day_config) in file.days {
+            let day = parse_day(&day).map_err(|e| format!("[days.{day}]: {e}"))?;
+            day_config.input = day_config.input.map(resolve);
+
+            config.days.insert(day, day_config);
+        }
+
+        Ok(config)
+    }
+
+    /// The configuration of the year's directory `dir`, from its `aoc.toml`; the defaults when it has none
+    pub fn load(dir: &Path) -> Result<Config, String> {
+        let path = dir.join(CONFIG_FILE);
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => Config::parse(&contents, dir)
+                .map_err(|e| format!("invalid {}: {e}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::new(dir.to_path_buf())),
+            Err(e) => Err(format!("failed to read {}: {e}", path.display())),
+        }
+    }
+
+    pub fn day(&self, day: u8) -> DayConfig {
+        self.days.get(&day).cloned().unwrap_or_default()
+    }
+
+    /// Where the puzzle input of `day` is: its `input` setting, or `<input_dir>/<year>/day<N>.txt`
+    pub fn input_path(&self, year: u32, day: u8) -> PathBuf {
+        self.day(day)
+            .input
+            .unwrap_or_else(|| self.input_dir.join(format!("{year}/day{day}.txt")))
+    }
+
+    pub fn profiles_dir(&self) -> PathBuf {
+        self.dir.join(PROFILES_DIR)
+    }
+
+    /// The session cookie in `session_file`, if one is set. It is a credential, so the file must not be readable by
+    /// anyone but its owner.
+    pub fn session(&self) -> Result<Option<String>, String> {
+        let Some(path) = &self.session_file else {
+            return Ok(None);
+        };
+
+        let metadata = fs::metadata(path).map_err(|e| {
+            format!(
+                "session_file {} in {CONFIG_FILE} cannot be read: {e}",
+                path.display()
+            )
+        })?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mode = metadata.permissions().mode() & 0o777;
+
+            if mode & 0o077 != 0 {
+                return Err(format!(
+                    "session_file {} can be read by others (mode {mode:o}); run `chmod 600 {}`",
+                    path.display(),
+                    path.display()
+                ));
+            }
+        }
+
+        #[cfg(not(unix))]
+        let _ = metadata;
+
+        let session = fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        let session = session.trim();
+
+        if session.is_empty() {
+            return Err(format!("session_file {} is empty", path.display()));
+        }
+
+        Ok(Some(session.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(
+            r#"
+year = 2024
+input_dir = "../inputs"
+session_file = "/etc/aoc/session"
+server = "http://localhost:8080"
+cache = true
+
+[days.6]
+input = "input/day6-large.txt"
+trim_trailing_blank_lines = false
+
+[days.day7]
+crlf_to_lf = false
+"#,
+            Path::new("2024"),
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(2024));
+        assert_eq!(config.input_dir, Path::new("2024/../inputs"));
+        assert_eq!(config.answers_dir, Path::new("2024/answers"));
+        assert_eq!(
+            config.session_file.as_deref(),
+            Some(Path::new("/etc/aoc/session"))
+        );
+        assert_eq!(config.server.as_deref(), Some("http://localhost:8080"));
+        assert!(config.cache);
+        assert_eq!(config.profiles_dir(), Path::new("2024/profiles"));
+
+        assert_eq!(
+            config.input_path(2024, 6),
+            Path::new("2024/input/day6-large.txt")
+        );
+        assert_eq!(
+            config.input_path(2024, 7),
+            Path::new("2024/../inputs/2024/day7.txt")
+        );
+        assert_eq!(
+            config.day(6).normalization(Normalization::ALL),
+            Normalization {
+                trim_trailing_blank_lines: false,
+                ..Normalization::ALL
+            }
+        );
+        assert!(!config.day(7).normalization(Normalization::ALL).crlf_to_lf);
+        assert_eq!(
+            config.day(8).normalization(Normalization::NONE),
+            Normalization::NONE
+        );
+
+        assert_eq!(Config::parse("", Path::new("")), Ok(Config::default()));
+        assert_eq!(
+            Config::default().input_path(2025, 1),
+            Path::new("input/2025/day1.txt")
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |contents: &str| Config::parse(contents, Path::new("")).unwrap_err();
+
+        assert_eq!(
+            error("year = 1999"),
+            "year 1999 is before the first Advent of Code, 2015"
+        );
+        assert_eq!(
+            error("server = \"adventofcode.com\""),
+            "server \"adventofcode.com\" is not an http:// or https:// URL"
+        );
+        assert_eq!(
+            error("[days.26]"),
+            "[days.26]: 26 is not a day between 1 and 25"
+        );
+        assert!(error("input = \"input\"").contains("unknown field `input`"));
+        assert!(error("[days.3]\ntrim = true").contains("unknown field `trim`"));
+        assert!(error("year = \"2024\"").contains("invalid type"));
+    }
+
+    #[test]
+    fn test_load() {
+        let dir = tempfile::tempdir().unwrap();
+
+        assert_eq!(
+            Config::load(dir.path()),
+            Ok(Config::new(dir.path().to_path_buf()))
+        );
+
+        fs::write(dir.path().join(CONFIG_FILE), "year = 1").unwrap();
+
+        let error = Config::load(dir.path()).unwrap_err();
+        assert!(error.starts_with("invalid "), "{error}");
+        assert!(error.ends_with("aoc.toml: year 1 is before the first Advent of Code, 2015"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_session() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session");
+
+        let config = Config {
+            session_file: Some(path.clone()),
+            ..Config::default()
+        };
+
+        assert_eq!(Config::default().session(), Ok(None));
+        assert!(config.session().unwrap_err().contains("cannot be read"));
+
+        fs::write(&path, "abc123\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        assert!(
+            config
+                .session()
+                .unwrap_err()
+                .contains("can be read by others (mode 644)")
+        );
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+        assert_eq!(config.session(), Ok(Some(String::from("abc123"))));
+
+        fs::write(&path, "\n").unwrap();
+        assert!(config.session().unwrap_err().ends_with("is empty"));
+    }
+}
@@ -0,0 +1,167 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// An example input with the answers the puzzle gives for it, stored as `fixtures/day<N>/<name>.toml`:
+///
+/// ```toml
+/// input = """
+/// 7 6 4 2 1
+/// 1 2 7 8 9
+/// """
+/// part1 = 2
+/// part2 = 4
+/// ```
+///
+/// A part without an answer is not run on the fixture, as some puzzles give a different example for each part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fixture {
+    pub name: String,
+    pub input: String,
+    pub answers: HashMap<u8, String>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct FixtureFile {
+    input: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part1: Option<toml::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    part2: Option<toml::Value>,
+}
+
+pub fn day_dir(day: u8) -> PathBuf {
+    PathBuf::from(format!("fixtures/day{day}"))
+}
+
+pub(crate) fn parse_answer(answer: toml::Value) -> Result<String, String> {
+    match answer {
+        toml::Value::String(answer) => Ok(answer),
+        toml::Value::Integer(answer) => Ok(answer.to_string()),
+        answer => Err(format!("answers must be strings or integers, not {answer}")),
+    }
+}
+
+pub(crate) fn to_answer_value(answer: &str) -> toml::Value {
+    answer
+        .parse::<i64>()
+        .map(toml::Value::Integer)
+        .unwrap_or_else(|_| toml::Value::String(answer.to_string()))
+}
+
+impl Fixture {
+    pub fn parse(name: &str, contents: &str) -> Result<Fixture, String> {
+        let file: FixtureFile = toml::from_str(contents).map_err(|e| e.to_string())?;
+
+        let mut answers = HashMap::new();
+
+        for (part, answer) in [(1, file.part1), (2, file.part2)] {
+            if let Some(answer) = answer {
+                answers.insert(part, parse_answer(answer)?);
+            }
+        }
+
+        Ok(Fixture {
+            name: name.to_string(),
+            input: file.input,
+            answers,
+        })
+    }
+
+    pub fn to_toml(&self) -> String {
+        let file = FixtureFile {
+            input: self.input.clone(),
+            part1: self.answers.get(&1).map(|answer| to_answer_value(answer)),
+            part2: self.answers.get(&2).map(|answer| to_answer_value(answer)),
+        };
+
+        toml::to_string(&file).unwrap()
+    }
+
+    /// Every fixture in `dir`, sorted by name; a missing directory has no fixtures
+    pub fn load_dir(dir: &Path) -> Result<Vec<Fixture>, String> {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("failed to read {}: {e}", dir.display())),
+        };
+
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "toml")
+            })
+            .collect();
+        paths.sort();
+
+        paths
+            .iter()
+            .map(|path| {
+                let name = path.file_stem().unwrap_or_default().to_string_lossy();
+
+                fs::read_to_string(path)
+                    .map_err(|e| e.to_string())
+                    .and_then(|contents| Fixture::parse(&name, &contents))
+                    .map_err(|e| format!("invalid fixture {}: {e}", path.display()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let fixture = Fixture::parse(
+            "example",
+            r#"
+input = """
+1 2
+3 4
+"""
+part1 = 10
+part2 = "abc"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(fixture.input, "1 2\n3 4\n");
+        assert_eq!(fixture.answers[&1], "10");
+        assert_eq!(fixture.answers[&2], "abc");
+    }
+
+    #[test]
+    fn test_parse_single_part() {
+        let fixture = Fixture::parse("example", "input = 'xmul(2,4)'\npart2 = 8").unwrap();
+
+        assert_eq!(fixture.answers.get(&1), None);
+        assert_eq!(fixture.answers[&2], "8");
+    }
+
+    #[test]
+    fn test_to_toml_round_trip() {
+        let fixture = Fixture {
+            name: String::from("example"),
+            input: String::from("a \"b\"\n\\c\n"),
+            answers: HashMap::from([(1, String::from("143")), (2, String::from("a,b"))]),
+        };
+
+        let contents = fixture.to_toml();
+
+        assert!(contents.contains("part1 = 143"));
+        assert_eq!(Fixture::parse("example", &contents), Ok(fixture));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(Fixture::parse("example", "part1 = 1").is_err());
+        assert!(Fixture::parse("example", "input = ''\npart1 = 1.5").is_err());
+    }
+}
@@ -0,0 +1,245 @@
+use std::{
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    str,
+};
+
+use aoc_runner::ArcStr;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use crate::{
+    fixture::Fixture,
+    input::Normalization,
+    runner::{self, Solution},
+};
+
+pub const CORPUS_DIR: &str = "fuzz/corpus/solutions";
+
+/// Feeds `data` to every solution the way the runner would. A malformed input should come back as an error; a panic
+/// is a bug and is left for the fuzzer to catch. Data that is not UTF-8 can never reach a solution, so it is skipped.
+pub fn run_solutions(solutions: &[Solution], data: &[u8]) {
+    let Ok(input) = str::from_utf8(data) else {
+        return;
+    };
+
+    let input = ArcStr::from(&Normalization::ALL.apply(input));
+
+    for solution in solutions {
+        if let Ok(runner) = (solution.factory)(input.clone()) {
+            let _ = runner.try_run();
+        }
+    }
+}
+
+/// The input of every fixture under `fixtures_dir`, named like `day3-example`
+pub fn seed_corpus(fixtures_dir: &Path) -> Result<Vec<(String, String)>, String> {
+    let entries = fs::read_dir(fixtures_dir)
+        .map_err(|e| format!("failed to read {}: {e}", fixtures_dir.display()))?;
+
+    let mut day_dirs: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_dir()
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| runner::parse_day(name).is_ok())
+        })
+        .collect();
+    day_dirs.sort();
+
+    let mut corpus = Vec::new();
+
+    for day_dir in day_dirs {
+        let day_name = day_dir.file_name().unwrap().to_string_lossy().into_owned();
+
+        for fixture in Fixture::load_dir(&day_dir)? {
+            corpus.push((format!("{day_name}-{}", fixture.name), fixture.input));
+        }
+    }
+
+    Ok(corpus)
+}
+
+/// Writes the seed corpus of `fixtures_dir` to `corpus_dir`, returning how many inputs were written
+pub fn write_seed_corpus(fixtures_dir: &Path, corpus_dir: &Path) -> Result<usize, String> {
+    let corpus = seed_corpus(fixtures_dir)?;
+
+    fs::create_dir_all(corpus_dir)
+        .map_err(|e| format!("failed to create {}: {e}", corpus_dir.display()))?;
+
+    for (name, input) in &corpus {
+        let path = corpus_dir.join(name);
+
+        fs::write(&path, input).map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+    }
+
+    Ok(corpus.len())
+}
+
+// characters that mean something to at least one puzzle, plus a few that mean nothing to any of them
+const INTERESTING: &[&str] = &[
+    "0",
+    "9",
+    "-",
+    " ",
+    "\n",
+    "\n\n",
+    ",",
+    "|",
+    "#",
+    "^",
+    ".",
+    "(",
+    ")",
+    "L",
+    "R",
+    "x",
+    "é",
+    "99999999999999999999",
+];
+
+/// `num_mutations` inputs made from `seed` by a few random edits each: deleting, duplicating or swapping lines and
+/// characters, truncating, and inserting characters that puzzles care about. A cheap stand-in for a fuzzer that runs
+/// on stable Rust as part of the tests.
+pub fn mutations(seed: &str, num_mutations: usize, rng_seed: u64) -> Vec<String> {
+    let mut rng = StdRng::seed_from_u64(rng_seed);
+
+    (0..num_mutations)
+        .map(|_| {
+            let mut chars: Vec<char> = seed.chars().collect();
+
+            for _ in 0..1 + rng.random_range(0..3) {
+                let i = rng.random_range(0..chars.len() + 1);
+
+                match rng.random_range(0..6) {
+                    0 => chars.truncate(i),
+                    1 if i < chars.len() => {
+                        chars.remove(i);
+                    }
+                    2 if i < chars.len() => chars.insert(i, chars[i]),
+                    3 => {
+                        let mut lines: Vec<String> = chars
+                            .iter()
+                            .collect::<String>()
+                            .split('\n')
+                            .map(String::from)
+                            .collect();
+                        let (a, b) = (
+                            rng.random_range(0..lines.len()),
+                            rng.random_range(0..lines.len()),
+                        );
+
+                        if rng.random_bool(0.5) {
+                            lines.swap(a, b);
+                        } else {
+                            lines.remove(a);
+                        }
+
+                        chars = lines.join("\n").chars().collect();
+                    }
+                    _ => {
+                        let inserted = INTERESTING[rng.random_range(0..INTERESTING.len())];
+                        chars.splice(i..i, inserted.chars());
+                    }
+                }
+            }
+
+            chars.into_iter().collect()
+        })
+        .collect()
+}
+
+/// The first panic of any solution on any of `inputs`, described with the solution and the input that caused it
+pub fn find_panic(solutions: &[Solution], inputs: &[String]) -> Option<String> {
+    for input in inputs {
+        for solution in solutions {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                run_solutions(&[*solution], input.as_bytes());
+            }));
+
+            if let Err(panic) = result {
+                return Some(format!(
+                    "{solution} panicked on {input:?}: {}",
+                    runner::panic_message(panic.as_ref())
+                ));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Borrow, error::Error, fmt};
+
+    use super::*;
+
+    // answers with the first byte of the input, and panics on an empty input
+    struct FirstByte(ArcStr);
+
+    impl aoc_runner::Runner for FirstByte {
+        fn r#gen(input: ArcStr) -> Self {
+            FirstByte(input)
+        }
+
+        fn run(&self) -> Box<dyn fmt::Display> {
+            Box::new(Borrow::<[u8]>::borrow(&self.0)[0])
+        }
+
+        fn bench(&self, _: fn(&dyn fmt::Display)) {}
+    }
+
+    fn first_byte_factory(input: ArcStr) -> Result<Box<dyn aoc_runner::Runner>, Box<dyn Error>> {
+        Ok(Box::new(<FirstByte as aoc_runner::Runner>::r#gen(input)))
+    }
+
+    const SOLUTION: Solution = Solution {
+        day: 1,
+        part: 2,
+        name: None,
+        factory: first_byte_factory,
+    };
+
+    #[test]
+    fn test_find_panic() {
+        assert_eq!(find_panic(&[SOLUTION], &[String::from("abc")]), None);
+
+        let panic = find_panic(&[SOLUTION], &[String::from("abc"), String::from("\n")]).unwrap();
+
+        assert!(panic.starts_with("Day 1 - Part 2 panicked on \"\\n\": index out of bounds"));
+    }
+
+    #[test]
+    fn test_mutations() {
+        let seed = "1 2\n3 4\n5 6";
+        let mutations = mutations(seed, 100, 3);
+
+        assert_eq!(mutations, super::mutations(seed, 100, 3));
+        assert!(mutations.iter().filter(|m| *m != seed).count() > 90);
+    }
+
+    #[test]
+    fn test_write_seed_corpus() {
+        let dir = tempfile::tempdir().unwrap();
+        let fixtures_dir = dir.path().join("fixtures");
+
+        fs::create_dir_all(fixtures_dir.join("day2")).unwrap();
+        fs::create_dir_all(fixtures_dir.join("notes")).unwrap();
+        fs::write(
+            fixtures_dir.join("day2/example.toml"),
+            "input = \"1 2\"\npart1 = 1\n",
+        )
+        .unwrap();
+
+        let corpus_dir = dir.path().join(CORPUS_DIR);
+
+        assert_eq!(write_seed_corpus(&fixtures_dir, &corpus_dir), Ok(1));
+        assert_eq!(
+            fs::read_to_string(corpus_dir.join("day2-example")).unwrap(),
+            "1 2"
+        );
+    }
+}
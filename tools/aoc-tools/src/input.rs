@@ -0,0 +1,165 @@
+use std::{error::Error, fmt, str::FromStr};
+/// How the runner cleans up a puzzle input before any `#[aoc_generator]` or `#[aoc]` function sees it. Every step is
+/// on by default; days where whitespace matters can turn steps off with `Runner::normalization`.
+///
+/// Whatever the normalization, aoc-runner's `ArcStr` always trims trailing `\n`s when the input is handed over.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Normalization {
+    /// Remove a leading UTF-8 byte order mark
+    pub strip_bom: bool,
+    /// Convert Windows `\r\n` line endings to `\n`
+    pub crlf_to_lf: bool,
+    /// Remove blank (empty or whitespace only) lines at the end of the input, along with the newline ending the last
+    /// line
+    pub trim_trailing_blank_lines: bool,
+}
+
+const BOM: char = '\u{feff}';
+
+impl Normalization {
+    pub const ALL: Normalization = Normalization {
+        strip_bom: true,
+        crlf_to_lf: true,
+        trim_trailing_blank_lines: true,
+    };
+
+    pub const NONE: Normalization = Normalization {
+        strip_bom: false,
+        crlf_to_lf: false,
+        trim_trailing_blank_lines: false,
+    };
+
+    pub fn apply(&self, input: &str) -> String {
+        let mut input = if self.strip_bom {
+            input.strip_prefix(BOM).unwrap_or(input)
+        } else {
+            input
+        };
+
+        if self.trim_trailing_blank_lines {
+            // a blank line is only trimmed as a whole so trailing whitespace on the last real line survives
+            while let Some(line_end) = input.rfind('\n') {
+                if !input[line_end + 1..].trim().is_empty() {
+                    break;
+                }
+
+                input = input[..line_end]
+                    .strip_suffix('\r')
+                    .unwrap_or(&input[..line_end]);
+            }
+
+            if input.trim().is_empty() {
+                input = "";
+            }
+        }
+
+        if self.crlf_to_lf {
+            input.replace("\r\n", "\n")
+        } else {
+            input.to_string()
+        }
+    }
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Normalization::ALL
+    }
+}
+
+/// A puzzle input that is not in the shape a solution expects. Generators and parts return this instead of panicking,
+/// so the runner reports which line is wrong and the fuzz targets can tell a malformed input from a bug.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputError {
+    /// Index of the offending line, counting from 0
+    pub line_i: Option<usize>,
+    pub message: String,
+}
+
+impl InputError {
+    pub fn new(message: impl Into<String>) -> InputError {
+        InputError {
+            line_i: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn at_line(line_i: usize, message: impl Into<String>) -> InputError {
+        InputError {
+            line_i: Some(line_i),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line_i {
+            Some(line_i) => write!(f, "line {}: {}", line_i + 1, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl Error for InputError {}
+
+/// Parses one number of line `line_i`
+pub fn parse_number<T: FromStr>(number: &str, line_i: usize) -> Result<T, InputError> {
+    number
+        .parse()
+        .map_err(|_| InputError::at_line(line_i, format!("{number:?} is not a valid number")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all() {
+        assert_eq!(Normalization::ALL.apply("\u{feff}ab\r\ncd\r\n"), "ab\ncd");
+        assert_eq!(Normalization::ALL.apply("ab\ncd\n\n  \n"), "ab\ncd");
+        assert_eq!(Normalization::ALL.apply("ab\r\n\r\n"), "ab");
+        assert_eq!(Normalization::ALL.apply("  ab  \ncd  \n"), "  ab  \ncd  ");
+        assert_eq!(Normalization::ALL.apply("ab\n\ncd"), "ab\n\ncd");
+        assert_eq!(Normalization::ALL.apply(" \n\n"), "");
+    }
+
+    #[test]
+    fn test_input_error() {
+        assert_eq!(parse_number::<u8>("12", 0), Ok(12));
+        assert_eq!(
+            parse_number::<u8>("300", 4).unwrap_err().to_string(),
+            "line 5: \"300\" is not a valid number"
+        );
+    }
+
+    #[test]
+    fn test_none() {
+        let input = "\u{feff}ab\r\ncd\r\n\n";
+
+        assert_eq!(Normalization::NONE.apply(input), input);
+    }
+
+    #[test]
+    fn test_single_steps() {
+        let input = "\u{feff}ab\r\ncd\r\n \r\n";
+
+        let strip_bom = Normalization {
+            strip_bom: true,
+            ..Normalization::NONE
+        };
+        assert_eq!(strip_bom.apply(input), "ab\r\ncd\r\n \r\n");
+
+        let crlf_to_lf = Normalization {
+            crlf_to_lf: true,
+            ..Normalization::NONE
+        };
+        assert_eq!(crlf_to_lf.apply(input), "\u{feff}ab\ncd\n \n");
+
+        let trim_trailing_blank_lines = Normalization {
+            trim_trailing_blank_lines: true,
+            ..Normalization::NONE
+        };
+        assert_eq!(trim_trailing_blank_lines.apply(input), "\u{feff}ab\r\ncd");
+    }
+}
@@ -0,0 +1,26 @@
+pub mod alloc;
+pub mod answer;
+pub mod answers;
+pub mod cache;
+pub mod config;
+pub mod fixture;
+pub mod fuzz;
+pub mod input;
+pub mod profile;
+pub mod puzzle;
+pub mod repl;
+pub mod report;
+pub mod runner;
+pub mod status;
+pub mod synthetic;
+#[cfg(test)]
+mod test_solution;
+pub mod trace;
+pub mod visualize;
+pub mod watch;
+
+pub use aoc_tools_derive::{aoc_main, aoc_solutions, cached};
+
+// what the code `#[cached]` expands to calls the crate by name, also in its own tests
+#[cfg(test)]
+extern crate self as aoc_tools;
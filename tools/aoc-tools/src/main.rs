@@ -0,0 +1,316 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{self, ExitCode},
+};
+
+use aoc_tools::{
+    answers::AcceptedAnswers,
+    cache::{self, CACHE_DIR},
+    config::Config,
+    fixture, fuzz, profile,
+    puzzle::{self, PuzzleServer},
+    runner::parse_day,
+    status, watch,
+};
+use clap::{Args, Parser, Subcommand};
+
+#[derive(Parser)]
+#[command(
+    about = "Tools for working on Advent of Code solutions; run from a year's crate directory"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Args)]
+struct PuzzleArgs {
+    #[arg(value_parser = parse_day)]
+    day: u8,
+
+    /// Defaults to the `year` of aoc.toml, or the name of the current directory, e.g. 2024
+    #[arg(long)]
+    year: Option<u16>,
+
+    /// Server to fetch descriptions from instead of $AOC_SERVER, the `server` of aoc.toml or https://adventofcode.com
+    #[arg(long, value_name = "URL")]
+    server: Option<String>,
+}
+
+impl PuzzleArgs {
+    fn year(&self, config: &Config) -> Result<u16, String> {
+        let crate_dir = env::current_dir().map_err(|e| e.to_string())?;
+
+        self.year
+            .or(config.year)
+            .or_else(|| puzzle::crate_year(&crate_dir))
+            .ok_or_else(|| {
+                String::from(
+                    "no year: pass --year, set `year` in aoc.toml or run from a year's directory",
+                )
+            })
+    }
+
+    fn load_description(&self, config: &Config, refresh: bool) -> Result<String, String> {
+        let server = PuzzleServer::from_config(self.server.as_deref(), config)?;
+
+        puzzle::load_description(&server, self.year(config)?, self.day, refresh)
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Rebuild and re-run a day's parts and examples whenever its source, input or fixtures change
+    Watch {
+        #[arg(value_parser = parse_day)]
+        day: u8,
+
+        /// Run a debug build, which compiles faster but runs slower, instead of a release build
+        #[arg(long)]
+        debug: bool,
+    },
+    /// Load a day's input through its generator and explore it with queries; runs `cargo run -- <day> --repl`
+    Repl {
+        #[arg(value_parser = parse_day)]
+        day: u8,
+
+        /// Explore the day's first fixture instead of its puzzle input
+        #[arg(long)]
+        examples: bool,
+    },
+    /// Write a synthetic input with the format, size and values of a day's input, and its answers by the current
+    /// solutions, to fixtures/day<N>/synthetic.toml, so that it can be committed where the real input may not be
+    Synthesize {
+        #[arg(value_parser = parse_day)]
+        day: u8,
+
+        #[arg(long, default_value_t = 1)]
+        seed: u64,
+
+        /// Overwrite the synthetic fixture if it already exists
+        #[arg(long)]
+        force: bool,
+    },
+    /// Store a day's description in puzzles/day<N>.html; set $AOC_SESSION or the `session_file` of aoc.toml to include
+    /// part 2
+    Download {
+        #[command(flatten)]
+        puzzle: PuzzleArgs,
+    },
+    /// Print a day's description as text, downloading it first if it is not stored yet
+    Read {
+        #[command(flatten)]
+        puzzle: PuzzleArgs,
+
+        /// Wrap lines at this many columns
+        #[arg(long, default_value_t = 100)]
+        width: usize,
+    },
+    /// Write the examples of a day's description and their answers to fixtures/day<N>/
+    Examples {
+        #[command(flatten)]
+        puzzle: PuzzleArgs,
+
+        /// Overwrite fixtures that already exist
+        #[arg(long)]
+        force: bool,
+    },
+    /// Record the answer the website accepted for a part, which runs are then checked against
+    Accept {
+        #[arg(value_parser = parse_day)]
+        day: u8,
+
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        answer: String,
+
+        /// Record it for the named profile in profiles/<NAME>/ instead of the default one
+        #[arg(long, value_name = "NAME")]
+        profile: Option<String>,
+    },
+    /// Remove what `#[cached]` generators stored in target/aoc-cache/
+    ClearCache,
+    /// Write the input of every fixture to the fuzz seed corpus, fuzz/corpus/solutions/ by default
+    Corpus {
+        #[arg(long, value_name = "DIR", default_value = fuzz::CORPUS_DIR)]
+        dir: PathBuf,
+    },
+    /// Show which days of every year are solved, with a star grid and run times
+    Status {
+        /// Only inspect the sources and accepted answers instead of running the Rust solutions
+        #[arg(long)]
+        no_run: bool,
+
+        /// Also write the report as a static HTML page
+        #[arg(long, value_name = "PATH")]
+        html: Option<PathBuf>,
+
+        /// Directory containing the year directories; defaults to the current directory or its parent
+        #[arg(long, value_name = "DIR")]
+        root: Option<PathBuf>,
+    },
+}
+
+fn write_examples(puzzle: &PuzzleArgs, config: &Config, is_force: bool) -> Result<(), String> {
+    let fixtures = puzzle::extract_fixtures(&puzzle.load_description(config, false)?);
+
+    if fixtures.is_empty() {
+        return Err(format!(
+            "no examples with answers found for day {}",
+            puzzle.day
+        ));
+    }
+
+    let dir = fixture::day_dir(puzzle.day);
+    fs::create_dir_all(&dir).map_err(|e| format!("failed to create {}: {e}", dir.display()))?;
+
+    for fixture in fixtures {
+        let path = dir.join(format!("{}.toml", fixture.name));
+
+        if path.exists() && !is_force {
+            println!(
+                "{} already exists, skipping (--force overwrites it)",
+                path.display()
+            );
+            continue;
+        }
+
+        fs::write(&path, fixture.to_toml())
+            .map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+
+        let mut parts: Vec<String> = fixture
+            .answers
+            .iter()
+            .map(|(part, answer)| format!("part {part} = {answer}"))
+            .collect();
+        parts.sort();
+
+        println!("wrote {} ({})", path.display(), parts.join(", "));
+    }
+
+    Ok(())
+}
+
+// runs the year's runner with `runner_args`, in release mode as they may run solutions on the puzzle input
+fn cargo_run(runner_args: &[&str]) -> Result<(), String> {
+    let args = [&["run", "--release", "--quiet", "--"], runner_args].concat();
+
+    let status = process::Command::new("cargo")
+        .args(&args)
+        .status()
+        .map_err(|e| format!("failed to run cargo: {e}"))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("cargo {} failed ({status})", args.join(" ")))
+    }
+}
+
+fn show_status(
+    is_run: bool,
+    html_path: Option<&Path>,
+    root: Option<PathBuf>,
+) -> Result<(), String> {
+    let root = match root {
+        Some(root) => root,
+        None => {
+            let current_dir = env::current_dir().map_err(|e| e.to_string())?;
+
+            match (puzzle::crate_year(&current_dir), current_dir.parent()) {
+                (Some(_), Some(parent)) => parent.to_path_buf(),
+                _ => current_dir,
+            }
+        }
+    };
+
+    let years = status::inspect_all(&root, is_run)?;
+
+    print!("{}", status::render_text(&years));
+
+    if let Some(html_path) = html_path {
+        fs::write(html_path, status::render_html(&years))
+            .map_err(|e| format!("failed to write {}: {e}", html_path.display()))?;
+
+        println!("wrote {}", html_path.display());
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    // run from a year's directory, whose aoc.toml configures every command
+    let config = match Config::load(Path::new("")) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = match cli.command {
+        Command::Watch { day, debug } => watch::watch(&config, day, debug),
+        Command::Repl { day, examples } => {
+            let day = day.to_string();
+            let mut args = vec![day.as_str(), "--repl"];
+
+            if examples {
+                args.push("--examples");
+            }
+
+            cargo_run(&args)
+        }
+        Command::Synthesize { day, seed, force } => {
+            let (day, seed) = (day.to_string(), seed.to_string());
+            let mut args = vec![day.as_str(), "--synthesize", "--seed", &seed];
+
+            if force {
+                args.push("--force");
+            }
+
+            cargo_run(&args)
+        }
+        Command::Download { puzzle } => puzzle.load_description(&config, true).map(|_| {
+            println!("wrote {}", puzzle::description_path(puzzle.day).display());
+        }),
+        Command::Read { puzzle, width } => puzzle
+            .load_description(&config, false)
+            .and_then(|html| puzzle::render_text(&html, width))
+            .map(|text| print!("{text}")),
+        Command::Examples { puzzle, force } => write_examples(&puzzle, &config, force),
+        Command::Accept {
+            day,
+            part,
+            answer,
+            profile,
+        } => AcceptedAnswers::accept(
+            &profile::answers_dir(&config, profile.as_deref()),
+            day,
+            part,
+            &answer,
+        ),
+        Command::ClearCache => {
+            cache::clear(&config.dir.join(CACHE_DIR)).map(|(num_entries, num_bytes)| {
+                println!(
+                    "removed {num_entries} cached generator outputs ({} KiB)",
+                    num_bytes.div_ceil(1024)
+                );
+            })
+        }
+        Command::Corpus { dir } => fuzz::write_seed_corpus(Path::new("fixtures"), &dir)
+            .map(|num_inputs| println!("wrote {num_inputs} inputs to {}", dir.display())),
+        Command::Status { no_run, html, root } => show_status(!no_run, html.as_deref(), root),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    answers::ANSWERS_DIR,
+    config::{Config, INPUT_DIR},
+};
+
+/// Where named profiles live: separate sets of puzzle inputs and accepted answers, e.g. of several accounts. The
+/// default profile is what `aoc.toml` configures; a named one has the default layout of a year's directory in
+/// `profiles/<name>/`: `profiles/<name>/input/<year>/day<N>.txt` and `profiles/<name>/answers/day<N>.toml`.
+pub const PROFILES_DIR: &str = "profiles";
+
+pub fn input_path(config: &Config, profile: Option<&str>, year: u32, day: u8) -> PathBuf {
+    match profile {
+        Some(name) => config
+            .profiles_dir()
+            .join(name)
+            .join(format!("{INPUT_DIR}/{year}/day{day}.txt")),
+        None => config.input_path(year, day),
+    }
+}
+
+pub fn answers_dir(config: &Config, profile: Option<&str>) -> PathBuf {
+    match profile {
+        Some(name) => config.profiles_dir().join(name).join(ANSWERS_DIR),
+        None => config.answers_dir.clone(),
+    }
+}
+
+/// The names of the profiles in `profiles_dir`, sorted; none when it does not exist
+pub fn list(profiles_dir: &Path) -> Result<Vec<String>, String> {
+    let entries = match fs::read_dir(profiles_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("failed to read {}: {e}", profiles_dir.display())),
+    };
+
+    let mut names = Vec::new();
+
+    for entry in entries {
+        let entry = entry.map_err(|e| e.to_string())?;
+
+        if entry.path().is_dir() {
+            names.push(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+
+    names.sort();
+
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paths() {
+        let config = Config::parse("input_dir = \"../inputs\"", Path::new("2024")).unwrap();
+
+        assert_eq!(
+            input_path(&config, None, 2024, 5),
+            Path::new("2024/../inputs/2024/day5.txt")
+        );
+        assert_eq!(
+            input_path(&config, Some("work"), 2024, 5),
+            Path::new("2024/profiles/work/input/2024/day5.txt")
+        );
+        assert_eq!(answers_dir(&config, None), Path::new("2024/answers"));
+        assert_eq!(
+            answers_dir(&config, Some("work")),
+            Path::new("2024/profiles/work/answers")
+        );
+    }
+
+    #[test]
+    fn test_list() {
+        let dir = tempfile::tempdir().unwrap();
+
+        assert_eq!(list(&dir.path().join("profiles")), Ok(Vec::new()));
+
+        for name in ["work", "home"] {
+            fs::create_dir_all(dir.path().join(name).join("input")).unwrap();
+        }
+        fs::write(dir.path().join("README.md"), "").unwrap();
+
+        assert_eq!(
+            list(dir.path()),
+            Ok(vec![String::from("home"), String::from("work")])
+        );
+    }
+}
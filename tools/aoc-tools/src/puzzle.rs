@@ -0,0 +1,302 @@
+use std::{
+    collections::HashMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use scraper::{ElementRef, Html, Selector};
+
+use crate::{config::Config, fixture::Fixture};
+
+pub const DEFAULT_SERVER: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = "github.com/jongrubb/advent-of-code aoc-tools";
+
+/// The Advent of Code website, or anything serving the same paths such as a local mock
+pub struct PuzzleServer {
+    url: String,
+    session: Option<String>,
+}
+
+impl PuzzleServer {
+    pub fn new(url: &str, session: Option<String>) -> PuzzleServer {
+        PuzzleServer {
+            url: url.trim_end_matches('/').to_string(),
+            session,
+        }
+    }
+
+    /// `url`, or `$AOC_SERVER`, or the `server` of `aoc.toml`, or the real website; the session cookie comes from
+    /// `$AOC_SESSION` or the `session_file` of `aoc.toml`. Without a session only part 1 of a description is served.
+    pub fn from_config(url: Option<&str>, config: &Config) -> Result<PuzzleServer, String> {
+        let env_url = env::var("AOC_SERVER").ok();
+        let url = url
+            .or(env_url.as_deref())
+            .or(config.server.as_deref())
+            .unwrap_or(DEFAULT_SERVER);
+
+        let session = match env::var("AOC_SESSION") {
+            Ok(session) => Some(session),
+            Err(_) => config.session()?,
+        };
+
+        Ok(PuzzleServer::new(url, session))
+    }
+
+    fn get(&self, path: &str) -> Result<String, String> {
+        let url = format!("{}{path}", self.url);
+
+        let mut request = ureq::get(&url).header("User-Agent", USER_AGENT);
+
+        if let Some(session) = &self.session {
+            request = request.header("Cookie", format!("session={session}"));
+        }
+
+        request
+            .call()
+            .and_then(|mut response| response.body_mut().read_to_string())
+            .map_err(|e| format!("GET {url} failed: {e}"))
+    }
+
+    pub fn fetch_description(&self, year: u16, day: u8) -> Result<String, String> {
+        self.get(&format!("/{year}/day/{day}"))
+    }
+}
+
+/// The year a crate directory is for, when it is named after it like `2024/`
+pub fn crate_year(crate_dir: &Path) -> Option<u16> {
+    crate_dir
+        .file_name()?
+        .to_str()?
+        .parse()
+        .ok()
+        .filter(|year| *year >= 2015)
+}
+
+pub fn description_path(day: u8) -> PathBuf {
+    PathBuf::from(format!("puzzles/day{day}.html"))
+}
+
+/// The stored description of a day, fetched and stored first if it is missing or `refresh` is set
+pub fn load_description(
+    server: &PuzzleServer,
+    year: u16,
+    day: u8,
+    refresh: bool,
+) -> Result<String, String> {
+    let path = description_path(day);
+
+    if !refresh {
+        match fs::read_to_string(&path) {
+            Ok(html) => return Ok(html),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(format!("failed to read {}: {e}", path.display())),
+        }
+    }
+
+    let html = server.fetch_description(year, day)?;
+
+    fs::create_dir_all(path.parent().unwrap())
+        .and_then(|()| fs::write(&path, &html))
+        .map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+
+    Ok(html)
+}
+
+fn selector(selector: &str) -> Selector {
+    Selector::parse(selector).unwrap()
+}
+
+// each part of a puzzle is an <article class="day-desc">; the rest of the page is navigation and sponsors
+fn part_articles(document: &Html) -> Vec<ElementRef<'_>> {
+    document.select(&selector("article.day-desc")).collect()
+}
+
+/// The description as plain text wrapped to `width` columns
+pub fn render_text(html: &str, width: usize) -> Result<String, String> {
+    let document = Html::parse_document(html);
+    let articles = part_articles(&document);
+
+    let html = if articles.is_empty() {
+        html.to_string()
+    } else {
+        articles.iter().map(|article| article.html()).collect()
+    };
+
+    html2text::from_read(html.as_bytes(), width).map_err(|e| e.to_string())
+}
+
+fn example_input(article: &ElementRef) -> Option<String> {
+    article
+        .select(&selector("pre > code"))
+        .next()
+        .map(|code| code.text().collect())
+}
+
+// the example's answer is usually the last emphasised code in the part: "... the total is <code><em>143</em></code>."
+fn example_answer(article: &ElementRef) -> Option<String> {
+    article
+        .select(&selector("code > em, em > code"))
+        .last()
+        .map(|answer| answer.text().collect::<String>().trim().to_string())
+}
+
+/// Fixtures guessed from a description: the first `<pre><code>` block of each part with the last emphasised answer of
+/// that part. Part 2 reuses the example of part 1 unless it has its own. Worth a look before relying on them.
+pub fn extract_fixtures(html: &str) -> Vec<Fixture> {
+    let document = Html::parse_document(html);
+
+    let mut fixtures: Vec<Fixture> = Vec::new();
+
+    for (part_i, article) in part_articles(&document).iter().enumerate().take(2) {
+        let part = part_i as u8 + 1;
+
+        let Some(answer) = example_answer(article) else {
+            continue;
+        };
+
+        match (example_input(article), fixtures.last_mut()) {
+            (None, Some(fixture)) => {
+                fixture.answers.insert(part, answer);
+            }
+            (None, None) => {}
+            (Some(input), _) => fixtures.push(Fixture {
+                name: if part == 1 {
+                    String::from("example")
+                } else {
+                    format!("example-part{part}")
+                },
+                input,
+                answers: HashMap::from([(part, answer)]),
+            }),
+        }
+    }
+
+    fixtures
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::*;
+
+    const DESCRIPTION: &str = r#"<!DOCTYPE html>
+<html><body>
+<header><h1>Advent of Code</h1></header>
+<main>
+<article class="day-desc"><h2>--- Day 5: Print Queue ---</h2>
+<p>The first section specifies the page ordering rules:</p>
+<pre><code>47|53
+97|13
+
+75,47,61
+</code></pre>
+<p>Adding the middle pages together produces <code><em>143</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Only the <em>incorrectly-ordered</em> updates count: <code><em>123</em></code>.</p>
+</article>
+</main>
+</body></html>
+"#;
+
+    #[test]
+    fn test_extract_fixtures_shared_example() {
+        let fixtures = extract_fixtures(DESCRIPTION);
+
+        assert_eq!(fixtures.len(), 1);
+        assert_eq!(fixtures[0].name, "example");
+        assert_eq!(fixtures[0].input, "47|53\n97|13\n\n75,47,61\n");
+        assert_eq!(fixtures[0].answers[&1], "143");
+        assert_eq!(fixtures[0].answers[&2], "123");
+    }
+
+    #[test]
+    fn test_extract_fixtures_separate_examples() {
+        let html = r#"
+<article class="day-desc"><pre><code>xmul(2,4)&amp;</code></pre><p><code><em>161</em></code></p></article>
+<article class="day-desc"><pre><code>don't()mul(8,5)</code></pre><p><em><code>48</code></em></p></article>
+"#;
+
+        let fixtures = extract_fixtures(html);
+
+        assert_eq!(fixtures.len(), 2);
+        assert_eq!(fixtures[0].input, "xmul(2,4)&");
+        assert_eq!(
+            fixtures[0].answers,
+            HashMap::from([(1, String::from("161"))])
+        );
+        assert_eq!(fixtures[1].name, "example-part2");
+        assert_eq!(
+            fixtures[1].answers,
+            HashMap::from([(2, String::from("48"))])
+        );
+    }
+
+    #[test]
+    fn test_render_text() {
+        let text = render_text(DESCRIPTION, 80).unwrap();
+
+        assert!(text.contains("Day 5: Print Queue"));
+        assert!(text.contains("97|13"));
+        assert!(text.contains("Part Two"));
+        assert!(!text.contains("Your puzzle answer"));
+    }
+
+    #[test]
+    fn test_crate_year() {
+        assert_eq!(crate_year(Path::new("/aoc/2024")), Some(2024));
+        assert_eq!(crate_year(Path::new("/aoc/tools")), None);
+    }
+
+    // serves one request and returns its request line and headers
+    fn mock_server(body: &'static str) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            let request: Vec<String> = BufReader::new(&stream)
+                .lines()
+                .map(Result::unwrap)
+                .take_while(|line| !line.is_empty())
+                .collect();
+
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            request
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn test_fetch_description() {
+        let (url, handle) = mock_server(DESCRIPTION);
+
+        let server = PuzzleServer::new(&url, Some(String::from("abc")));
+
+        assert_eq!(server.fetch_description(2024, 5).unwrap(), DESCRIPTION);
+
+        let request = handle.join().unwrap();
+
+        assert_eq!(request[0], "GET /2024/day/5 HTTP/1.1");
+        assert!(
+            request
+                .iter()
+                .any(|header| header.eq_ignore_ascii_case("cookie: session=abc"))
+        );
+    }
+}
@@ -0,0 +1,363 @@
+use std::{
+    borrow::Borrow,
+    error::Error,
+    fmt,
+    io::{BufRead, Write},
+    ops::RangeInclusive,
+};
+
+use aoc_runner::ArcStr;
+
+use crate::runner::{self, Solution};
+
+/// What a query found, printed with its `Debug` output
+pub type QueryResult<'a> = Result<Box<dyn fmt::Debug + 'a>, String>;
+
+/// A day's parsed input as the REPL explores it. `show` prints its `Debug` output; the queries are all optional, and a
+/// day only implements the ones that make sense for its input.
+pub trait Explore: fmt::Debug {
+    /// The cell of a grid at `row`, `column`, both counted from 0
+    fn cell(&self, _row: usize, _column: usize) -> QueryResult<'_> {
+        Err(unsupported("cell"))
+    }
+
+    /// The cells next to `row`, `column`
+    fn neighbours(&self, _row: usize, _column: usize) -> QueryResult<'_> {
+        Err(unsupported("neighbours"))
+    }
+
+    /// The rules that mention `key`, e.g. a page number
+    fn rules(&self, _key: &str) -> QueryResult<'_> {
+        Err(unsupported("rules"))
+    }
+}
+
+fn unsupported(query: &str) -> String {
+    format!("this day's input does not support `{query}`")
+}
+
+/// Parses a day's input the way its generator does, into something the REPL can explore
+pub type Explorer = fn(&str) -> Result<Box<dyn Explore>, Box<dyn Error>>;
+
+const HELP: &str = "\
+show                            the parsed input
+cell <row> <column>             a grid cell, counted from 0
+neighbours <row> <column>       the cells next to it
+rules <key>                     the rules that mention key
+lines [<filter>]                the input lines, filtered
+run <part> [<filter>]           run a part on the input lines, filtered
+help, quit
+
+<filter> is a range of lines like 1-20 (counted from 1) and/or `matching <text>`, e.g. `run 1 1-20 matching 47`.
+Blank lines are always kept, as they separate the sections of some inputs.";
+
+/// Which lines of the input a command uses; blank lines always stay
+#[derive(Debug, Default, PartialEq)]
+struct LineFilter {
+    lines: Option<RangeInclusive<usize>>,
+    matching: Option<String>,
+}
+
+impl LineFilter {
+    fn parse(args: &[&str]) -> Result<LineFilter, String> {
+        let mut filter = LineFilter::default();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match *arg {
+                // the rest of the command, so that the text can contain spaces
+                "matching" => {
+                    let text: Vec<&str> = args.by_ref().copied().collect();
+
+                    if text.is_empty() {
+                        return Err(String::from("expected matching <text>"));
+                    }
+
+                    filter.matching = Some(text.join(" "));
+                }
+                range => {
+                    let (first, last) = range
+                        .split_once('-')
+                        .and_then(|(first, last)| Some((first.parse().ok()?, last.parse().ok()?)))
+                        .ok_or_else(|| format!("{range:?} is not a range of lines like 1-20"))?;
+
+                    filter.lines = Some(first..=last);
+                }
+            }
+        }
+
+        Ok(filter)
+    }
+
+    fn apply<'a>(&self, input: &'a str) -> Vec<&'a str> {
+        input
+            .lines()
+            .enumerate()
+            .filter(|(line_i, line)| {
+                line.is_empty()
+                    || self
+                        .lines
+                        .as_ref()
+                        .is_none_or(|lines| lines.contains(&(line_i + 1)))
+                        && self
+                            .matching
+                            .as_ref()
+                            .is_none_or(|text| line.contains(text.as_str()))
+            })
+            .map(|(_, line)| line)
+            .collect()
+    }
+}
+
+fn parse_position(args: &[&str]) -> Result<(usize, usize), String> {
+    match args {
+        [row, column] => row
+            .parse()
+            .ok()
+            .zip(column.parse().ok())
+            .ok_or_else(|| format!("{row} {column} is not a row and column")),
+        _ => Err(String::from("expected <row> <column>")),
+    }
+}
+
+/// A day's input loaded for exploring
+pub struct Session<'s> {
+    day: u8,
+    input: ArcStr,
+    explored: Result<Box<dyn Explore>, String>,
+    solutions: &'s [Solution],
+}
+
+impl<'s> Session<'s> {
+    /// `solutions` are the parts `run` can run; `explorer` parses the input for the queries, if the day has one
+    pub fn new(
+        day: u8,
+        input: ArcStr,
+        explorer: Option<Explorer>,
+        solutions: &'s [Solution],
+    ) -> Session<'s> {
+        let explored = match explorer {
+            Some(explorer) => explorer(input.borrow()).map_err(|e| format!("failed to parse: {e}")),
+            None => Err(format!(
+                "day {day} has no explorer; only lines and run work"
+            )),
+        };
+
+        Session {
+            day,
+            input,
+            explored,
+            solutions,
+        }
+    }
+
+    fn explored(&self) -> Result<&dyn Explore, String> {
+        self.explored.as_deref().map_err(Clone::clone)
+    }
+
+    /// What one command line prints; `None` to quit
+    fn eval(&self, line: &str) -> Option<String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+
+        let result = match words.as_slice() {
+            [] => return Some(String::new()),
+            ["quit" | "exit"] => return None,
+            ["help"] => Ok(String::from(HELP)),
+            ["show"] => self.explored().map(|explored| format!("{explored:#?}")),
+            ["cell", args @ ..] => parse_position(args).and_then(|(row, column)| {
+                self.explored()?
+                    .cell(row, column)
+                    .map(|cell| format!("{cell:?}"))
+            }),
+            ["neighbours", args @ ..] => parse_position(args).and_then(|(row, column)| {
+                self.explored()?
+                    .neighbours(row, column)
+                    .map(|neighbours| format!("{neighbours:?}"))
+            }),
+            ["rules", key] => self
+                .explored()
+                .and_then(|explored| explored.rules(key).map(|rules| format!("{rules:?}"))),
+            ["lines", args @ ..] => LineFilter::parse(args).map(|filter| {
+                let lines = filter.apply(self.input.borrow());
+
+                format!("{} lines\n{lines:#?}", lines.len())
+            }),
+            ["run", part, args @ ..] => {
+                LineFilter::parse(args).and_then(|filter| self.run(part, &filter))
+            }
+            _ => Err(String::from("unknown command; see help")),
+        };
+
+        Some(result.unwrap_or_else(|e| format!("error: {e}")))
+    }
+
+    fn run(&self, part: &str, filter: &LineFilter) -> Result<String, String> {
+        let solution = self
+            .solutions
+            .iter()
+            .find(|s| s.part.to_string() == part)
+            .ok_or_else(|| format!("day {} has no part {part}", self.day))?;
+
+        let lines = filter.apply(self.input.borrow());
+        let output = runner::run(solution, ArcStr::from(lines.join("\n").as_str()))?;
+
+        Ok(format!(
+            "{:?} on {} lines ({:?})",
+            output.answer,
+            lines.len(),
+            output.generator_time + output.runner_time
+        ))
+    }
+
+    /// Reads commands from `commands` until it ends or says quit, writing a prompt before each and the results to `out`
+    pub fn run_commands(&self, commands: impl BufRead, mut out: impl Write) -> std::io::Result<()> {
+        let prompt = format!("day{}> ", self.day);
+
+        write!(out, "{prompt}")?;
+        out.flush()?;
+
+        for line in commands.lines() {
+            let Some(result) = self.eval(&line?) else {
+                break;
+            };
+
+            if !result.is_empty() {
+                writeln!(out, "{result}")?;
+            }
+
+            write!(out, "{prompt}")?;
+            out.flush()?;
+        }
+
+        writeln!(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    // a grid of characters
+    #[derive(Debug)]
+    struct Grid(Vec<Vec<char>>);
+
+    impl Explore for Grid {
+        fn cell(&self, row: usize, column: usize) -> QueryResult<'_> {
+            self.0
+                .get(row)
+                .and_then(|cells| cells.get(column))
+                .map(|c| Box::new(c) as Box<dyn fmt::Debug>)
+                .ok_or_else(|| String::from("outside the grid"))
+        }
+    }
+
+    fn grid_explorer(input: &str) -> Result<Box<dyn Explore>, Box<dyn Error>> {
+        Ok(Box::new(Grid(
+            input.lines().map(|line| line.chars().collect()).collect(),
+        )))
+    }
+
+    // counts the lines of its input
+    struct CountLines(usize);
+
+    impl aoc_runner::Runner for CountLines {
+        fn r#gen(input: ArcStr) -> Self {
+            CountLines(Borrow::<str>::borrow(&input).lines().count())
+        }
+
+        fn run(&self) -> Box<dyn fmt::Display> {
+            Box::new(self.0)
+        }
+
+        fn bench(&self, _: fn(&dyn fmt::Display)) {}
+    }
+
+    fn count_lines_factory(input: ArcStr) -> Result<Box<dyn aoc_runner::Runner>, Box<dyn Error>> {
+        Ok(Box::new(<CountLines as aoc_runner::Runner>::r#gen(input)))
+    }
+
+    const SOLUTIONS: [Solution; 1] = [Solution {
+        day: 4,
+        part: 1,
+        name: None,
+        factory: count_lines_factory,
+    }];
+
+    fn run_commands(explorer: Option<Explorer>, commands: &str) -> String {
+        let session = Session::new(4, ArcStr::from("ab\ncd\nab"), explorer, &SOLUTIONS);
+        let mut out = Vec::new();
+
+        session
+            .run_commands(Cursor::new(commands), &mut out)
+            .unwrap();
+
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_queries() {
+        let out = run_commands(
+            Some(grid_explorer),
+            "cell 1 0\ncell 5 5\nneighbours 0 0\nrules a\nquit\ncell 0 0\n",
+        );
+
+        assert_eq!(
+            out,
+            "day4> 'c'\nday4> error: outside the grid\n\
+             day4> error: this day's input does not support `neighbours`\n\
+             day4> error: this day's input does not support `rules`\nday4> \n"
+        );
+    }
+
+    #[test]
+    fn test_run_filtered() {
+        let out = run_commands(
+            None,
+            "run 1\nrun 1 matching ab\nrun 1 2-3 matching a\nrun 2\nshow\n",
+        );
+        let results: Vec<&str> = out
+            .lines()
+            .map(|line| line.split(" (").next().unwrap())
+            .collect();
+
+        assert_eq!(
+            results,
+            [
+                "day4> \"3\" on 3 lines",
+                "day4> \"2\" on 2 lines",
+                "day4> \"1\" on 1 lines",
+                "day4> error: day 4 has no part 2",
+                "day4> error: day 4 has no explorer; only lines and run work",
+                "day4> ",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_line_filter() {
+        assert_eq!(LineFilter::parse(&[]), Ok(LineFilter::default()));
+        assert_eq!(
+            LineFilter::parse(&["2-5", "matching", "47|", "53", "1-2"]),
+            Ok(LineFilter {
+                lines: Some(2..=5),
+                matching: Some(String::from("47| 53 1-2")),
+            })
+        );
+        assert!(LineFilter::parse(&["3"]).is_err());
+        assert!(LineFilter::parse(&["everything"]).is_err());
+
+        let filter = LineFilter::parse(&["2-4", "matching", "|"]).unwrap();
+        assert_eq!(
+            filter.apply(
+                "1|2
+3|4
+
+1,2
+5|6"
+            ),
+            ["3|4", ""]
+        );
+    }
+}
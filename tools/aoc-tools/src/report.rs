@@ -0,0 +1,220 @@
+use std::{fmt, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::alloc::AllocStats;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SolutionOutput {
+    pub answer: String,
+    pub generator_time: Duration,
+    pub runner_time: Duration,
+    /// Only measured with `--memory`
+    #[serde(default)]
+    pub generator_memory: Option<AllocStats>,
+    #[serde(default)]
+    pub runner_memory: Option<AllocStats>,
+}
+
+impl SolutionOutput {
+    pub fn new(answer: String, generator_time: Duration, runner_time: Duration) -> SolutionOutput {
+        SolutionOutput {
+            answer,
+            generator_time,
+            runner_time,
+            generator_memory: None,
+            runner_memory: None,
+        }
+    }
+
+    fn timings(&self) -> String {
+        let format = |time: Duration, memory: Option<AllocStats>| match memory {
+            Some(memory) => format!("{time:?} ({memory})"),
+            None => format!("{time:?}"),
+        };
+
+        format!(
+            "\tgenerator: {},\n\trunner: {}",
+            format(self.generator_time, self.generator_memory),
+            format(self.runner_time, self.runner_memory)
+        )
+    }
+}
+
+/// The result of running one solution on the puzzle input or on a fixture. With `--json` the runner prints one of
+/// these per line, which is how `aoc watch` reads them back.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SolutionReport {
+    pub day: u8,
+    pub part: u8,
+    pub name: Option<String>,
+    /// `None` for the puzzle input
+    pub fixture: Option<String>,
+    /// The named profile whose puzzle input this ran on; `None` for the default profile and for fixtures
+    #[serde(default)]
+    pub profile: Option<String>,
+    pub expected: Option<String>,
+    pub output: Result<SolutionOutput, String>,
+}
+
+impl SolutionReport {
+    pub fn answer(&self) -> Option<&str> {
+        self.output
+            .as_ref()
+            .ok()
+            .map(|output| output.answer.as_str())
+    }
+
+    /// Whether the answer matches the fixture's expected answer; `None` when nothing is expected
+    pub fn is_correct(&self) -> Option<bool> {
+        self.expected
+            .as_ref()
+            .map(|expected| self.answer() == Some(expected))
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.output.is_ok() && self.is_correct() != Some(false)
+    }
+
+    /// "Day N - Part M[ - name][ [fixture]][ (profile)]"
+    pub fn label(&self) -> String {
+        let mut label = format!("Day {} - Part {}", self.day, self.part);
+
+        if let Some(name) = &self.name {
+            label += &format!(" - {name}");
+        }
+
+        if let Some(fixture) = &self.fixture {
+            label += &format!(" [{fixture}]");
+        }
+
+        if let Some(profile) = &self.profile {
+            label += &format!(" ({profile})");
+        }
+
+        label
+    }
+}
+
+impl fmt::Display for SolutionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.output, &self.expected) {
+            (Ok(output), Some(_)) if self.is_correct() == Some(true) => writeln!(
+                f,
+                "{}: {} (ok)\n{}",
+                self.label(),
+                output.answer,
+                output.timings()
+            )?,
+            (Ok(output), Some(expected)) => writeln!(
+                f,
+                "{}: {} (WRONG, expected {expected})",
+                self.label(),
+                output.answer
+            )?,
+            (Ok(output), None) => writeln!(
+                f,
+                "{}: {}\n{}",
+                self.label(),
+                output.answer,
+                output.timings()
+            )?,
+            (Err(e), _) => writeln!(f, "{}: {e}", self.label())?,
+        }
+
+        Ok(())
+    }
+}
+
+/// The reports of every variant of one part on one input, the default first, to check that they agree and compare
+/// their timings
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariantComparison {
+    pub reports: Vec<SolutionReport>,
+}
+
+impl VariantComparison {
+    fn default_answer(&self) -> Option<&str> {
+        self.reports.first().and_then(SolutionReport::answer)
+    }
+
+    /// Every variant that failed or answered differently from the default
+    pub fn disagreements(&self) -> impl Iterator<Item = &SolutionReport> {
+        self.reports
+            .iter()
+            .skip(1)
+            .filter(|report| report.answer().is_none() || report.answer() != self.default_answer())
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.default_answer().is_some() && self.disagreements().next().is_none()
+    }
+}
+
+impl fmt::Display for VariantComparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(default) = self.reports.first() else {
+            return Ok(());
+        };
+
+        let label = SolutionReport {
+            name: None,
+            ..default.clone()
+        }
+        .label();
+
+        match self.default_answer() {
+            Some(answer) if self.is_success() => writeln!(
+                f,
+                "{label}: {} variants agree on {answer}",
+                self.reports.len()
+            )?,
+            _ => writeln!(f, "{label}: variants DISAGREE")?,
+        }
+
+        let names: Vec<&str> = self
+            .reports
+            .iter()
+            .map(|report| report.name.as_deref().unwrap_or("(default)"))
+            .collect();
+        let name_width = names.iter().map(|name| name.len()).max().unwrap_or(0);
+
+        let default_time = default
+            .output
+            .as_ref()
+            .ok()
+            .map(|output| output.generator_time + output.runner_time);
+
+        for (report, name) in self.reports.iter().zip(names) {
+            let output = match &report.output {
+                Ok(output) => output,
+                Err(e) => {
+                    writeln!(f, "\t{name:name_width$}  {}", e.lines().next().unwrap_or(e))?;
+                    continue;
+                }
+            };
+
+            let time = output.generator_time + output.runner_time;
+            let relative = match default_time {
+                Some(default_time) if !default_time.is_zero() => {
+                    format!("{:.2}x", time.as_secs_f64() / default_time.as_secs_f64())
+                }
+                _ => String::from("-"),
+            };
+
+            write!(
+                f,
+                "\t{name:name_width$}  {:>12}  {relative:>8}",
+                format!("{time:.2?}")
+            )?;
+
+            if report.answer() != self.default_answer() {
+                write!(f, "  answered {}", output.answer)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
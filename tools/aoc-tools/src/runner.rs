@@ -0,0 +1,1055 @@
+use std::{
+    any::Any,
+    borrow::Borrow,
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt, fs, io, iter,
+    num::NonZeroUsize,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Instant,
+};
+
+use aoc_runner::ArcStr;
+use clap::{ArgAction, Parser};
+
+use crate::{
+    alloc,
+    answer::{self, Overflow},
+    answers::AcceptedAnswers,
+    cache,
+    config::{CONFIG_FILE, Config},
+    fixture::{self, Fixture},
+    input::Normalization,
+    profile,
+    repl::{Explorer, Session},
+    report::{SolutionOutput, SolutionReport, VariantComparison},
+    synthetic::{self, Synthesizer},
+    trace,
+};
+
+pub type Factory = fn(ArcStr) -> Result<Box<dyn aoc_runner::Runner>, Box<dyn Error>>;
+
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub day: u8,
+    pub part: u8,
+    pub name: Option<&'static str>,
+    pub factory: Factory,
+}
+
+impl fmt::Display for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {} - Part {}", self.day, self.part)?;
+
+        if let Some(name) = self.name {
+            write!(f, " - {name}")?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn parse_day(day: &str) -> Result<u8, String> {
+    day.trim_start_matches("day")
+        .parse::<u8>()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .ok_or_else(|| format!("{day} is not a day between 1 and 25"))
+}
+
+#[derive(Parser)]
+#[command(about = "Runs the Advent of Code solutions registered with `#[aoc]`")]
+struct Args {
+    /// Day to run, e.g. `3` or `day3`; every day runs when omitted
+    #[arg(value_parser = parse_day)]
+    day: Option<u8>,
+
+    /// Part to run; both parts run when omitted
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Run every day, with the solutions spread over `--jobs` workers; reports keep their usual order
+    #[arg(long, conflicts_with_all = ["day", "part"])]
+    all: bool,
+
+    /// How many solutions run at once; defaults to the number of CPUs with `--all` and to 1 otherwise
+    #[arg(short, long)]
+    jobs: Option<NonZeroUsize>,
+
+    /// Time every solution again on its own after running them in parallel, so timings are not skewed by the others
+    #[arg(long)]
+    isolated_timing: bool,
+
+    /// Also run the named variants of every part, check that they agree with the default and compare their timings
+    #[arg(long)]
+    variants: bool,
+
+    /// Run the example fixtures in fixtures/day<N>/ and check their expected answers instead of the puzzle input
+    #[arg(long)]
+    examples: bool,
+
+    /// Run on this file instead of the puzzle input, e.g. someone else's input; there are no answers to check
+    #[arg(long, value_name = "PATH", requires = "day", conflicts_with_all = ["examples", "profile"])]
+    input: Option<PathBuf>,
+
+    /// Run on the input piped to stdin instead of the puzzle input, e.g. an example
+    #[arg(long, requires = "day", conflicts_with_all = ["examples", "input", "profile", "repl"])]
+    stdin: bool,
+
+    /// Run on the puzzle input of a named profile in profiles/<NAME>/ and check its accepted answers
+    #[arg(long, value_name = "NAME", conflicts_with = "examples")]
+    profile: Option<String>,
+
+    /// Run on the puzzle input of every profile in profiles/ and check that each gets the profile's accepted answers
+    #[arg(long, conflicts_with_all = ["examples", "input", "stdin", "profile", "repl", "synthesize"])]
+    all_profiles: bool,
+
+    /// Count the allocations, bytes allocated and peak live bytes of every generator and part
+    #[arg(long)]
+    memory: bool,
+
+    /// Reuse what `#[cached]` generators returned for the same input, from target/aoc-cache/; `cache = true` in
+    /// aoc.toml turns it on for every run
+    #[arg(long)]
+    cache: bool,
+
+    /// Explore the day's parsed input interactively instead of running it; with `--examples`, its first fixture
+    #[arg(long, requires = "day")]
+    repl: bool,
+
+    /// Write a synthetic stand-in for the day's input to fixtures/day<N>/synthetic.toml, answered by its solutions
+    #[arg(long, requires = "day", conflicts_with = "repl")]
+    synthesize: bool,
+
+    /// Seed of the synthetic input
+    #[arg(long, default_value_t = 1, requires = "synthesize")]
+    seed: u64,
+
+    /// Overwrite the synthetic fixture if it already exists
+    #[arg(long, requires = "synthesize")]
+    force: bool,
+
+    /// Print one JSON report per line instead of text
+    #[arg(long)]
+    json: bool,
+
+    /// Print solution traces; repeat for more detail (-v info, -vv debug, -vvv trace)
+    #[arg(short, long, action = ArgAction::Count)]
+    verbose: u8,
+
+    /// Print every trace of one day, e.g. `--trace day1` or `--trace day1=debug`
+    #[arg(long, value_name = "DAY[=LEVEL]")]
+    trace: Vec<String>,
+
+    /// Write traces to this file instead of stderr
+    #[arg(long, value_name = "PATH")]
+    trace_file: Option<PathBuf>,
+}
+
+/// How a run fails on a puzzle input that is not there, which `aoc status` does not count as the solution failing
+pub(crate) const MISSING_INPUT: &str = "no puzzle input at";
+
+fn read_file(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("failed to read {}: {e}", path.display()))
+}
+
+pub(crate) fn panic_message(panic: &(dyn Any + Send)) -> &str {
+    panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic")
+}
+
+// a panicking solution is reported like a failing one so the remaining solutions still run
+pub(crate) fn run(solution: &Solution, input: ArcStr) -> Result<SolutionOutput, String> {
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        answer::in_solution(solution.day, solution.part, || {
+            run_unguarded(solution, input)
+        })
+    }))
+    .unwrap_or_else(|panic| {
+        let message = panic_message(panic.as_ref());
+
+        // what debug builds panic with on integer overflow
+        if message.ends_with("with overflow") {
+            Err(format!("OVERFLOWED: {message}"))
+        } else {
+            Err(format!("PANICKED: {message}"))
+        }
+    })
+}
+
+/// The `answer::Overflow` behind `e`, if an answer outgrew its type, even when it is wrapped in another error
+fn find_overflow<'e>(e: &'e (dyn Error + 'static)) -> Option<&'e Overflow> {
+    iter::successors(Some(e), |&e| {
+        // an `io::Error` wrapping another error skips it in `source()`
+        match e.downcast_ref::<io::Error>().and_then(io::Error::get_ref) {
+            Some(inner) => Some(inner as &(dyn Error + 'static)),
+            None => e.source(),
+        }
+    })
+    .find_map(|e| e.downcast_ref::<Overflow>())
+}
+
+fn describe_error(stage: &str, e: &(dyn Error + 'static)) -> String {
+    match find_overflow(e) {
+        Some(overflow) => format!("OVERFLOWED while {stage}: {overflow}"),
+        None => format!("FAILED while {stage}:\n{e:#?}"),
+    }
+}
+
+fn run_unguarded(solution: &Solution, input: ArcStr) -> Result<SolutionOutput, String> {
+    let span = tracing::info_span!("solution", day = solution.day, part = solution.part);
+    let _entered = span.enter();
+
+    let start_time = Instant::now();
+
+    let (runner, generator_memory) = alloc::measure(|| (solution.factory)(input));
+    let runner = runner.map_err(|e| describe_error("generating", e.as_ref()))?;
+
+    let generated_time = Instant::now();
+
+    let (answer, runner_memory) = alloc::measure(|| runner.try_run());
+    let answer = answer.map_err(|e| describe_error("running", e.as_ref()))?;
+
+    let final_time = Instant::now();
+
+    Ok(SolutionOutput {
+        generator_memory,
+        runner_memory,
+        ..SolutionOutput::new(
+            answer.to_string(),
+            generated_time - start_time,
+            final_time - generated_time,
+        )
+    })
+}
+
+/// One solution on one input, prepared up front so that any worker can run it
+struct Job<'r> {
+    solution: &'r Solution,
+    fixture: Option<String>,
+    profile: Option<String>,
+    input: Result<ArcStr, String>,
+    expected: Option<String>,
+}
+
+impl Job<'_> {
+    fn run(&self) -> SolutionReport {
+        let output = self
+            .input
+            .clone()
+            .and_then(|input| run(self.solution, input));
+
+        SolutionReport {
+            day: self.solution.day,
+            part: self.solution.part,
+            name: self.solution.name.map(String::from),
+            fixture: self.fixture.clone(),
+            profile: self.profile.clone(),
+            expected: self.expected.clone(),
+            output,
+        }
+    }
+}
+
+/// Runs `jobs` on `num_workers` threads and reports them in the order of `jobs`, whichever finishes first
+fn run_jobs(jobs: &[Job], num_workers: usize, on_report: &mut impl FnMut(SolutionReport)) {
+    if num_workers <= 1 {
+        jobs.iter().for_each(|job| on_report(job.run()));
+        return;
+    }
+
+    let next_job_i = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..num_workers.min(jobs.len()) {
+            let sender = sender.clone();
+            let next_job_i = &next_job_i;
+
+            scope.spawn(move || {
+                loop {
+                    let job_i = next_job_i.fetch_add(1, Ordering::Relaxed);
+
+                    let Some(job) = jobs.get(job_i) else {
+                        break;
+                    };
+
+                    let _ = sender.send((job_i, job.run()));
+                }
+            });
+        }
+
+        drop(sender);
+
+        let mut finished: BTreeMap<usize, SolutionReport> = BTreeMap::new();
+        let mut next_report_i = 0;
+
+        for (job_i, report) in receiver {
+            finished.insert(job_i, report);
+
+            while let Some(report) = finished.remove(&next_report_i) {
+                on_report(report);
+                next_report_i += 1;
+            }
+        }
+    });
+}
+
+// a part on one input: its day, part, fixture and profile
+type PartInput = (u8, u8, Option<String>, Option<String>);
+
+/// Groups the reports of the variants of each part by input, for the parts that have more than one variant. The
+/// default variant runs first, so it leads each group; a group is left out when the default failed, as its report
+/// already says so and there is nothing to compare against.
+fn compare_variants(reports: Vec<SolutionReport>) -> Vec<VariantComparison> {
+    let mut groups: BTreeMap<PartInput, Vec<SolutionReport>> = BTreeMap::new();
+
+    for report in reports {
+        groups
+            .entry((
+                report.day,
+                report.part,
+                report.fixture.clone(),
+                report.profile.clone(),
+            ))
+            .or_default()
+            .push(report);
+    }
+
+    groups
+        .into_values()
+        .filter(|reports| reports.len() > 1 && reports[0].answer().is_some())
+        .map(|reports| VariantComparison { reports })
+        .collect()
+}
+
+/// Runs `jobs` in parallel for their answers, then times every successful one again on its own so that its timings
+/// and allocations are not skewed by the others
+fn run_jobs_isolated(jobs: &[Job], num_workers: usize, on_report: &mut impl FnMut(SolutionReport)) {
+    let mut reports: Vec<SolutionReport> = Vec::with_capacity(jobs.len());
+    run_jobs(jobs, num_workers, &mut |report| reports.push(report));
+
+    for (job, mut report) in jobs.iter().zip(reports) {
+        if let (Ok(output), Ok(isolated)) = (&mut report.output, job.run().output) {
+            output.generator_time = isolated.generator_time;
+            output.runner_time = isolated.runner_time;
+            output.generator_memory = isolated.generator_memory;
+            output.runner_memory = isolated.runner_memory;
+        }
+
+        on_report(report);
+    }
+}
+
+pub struct Runner {
+    year: u32,
+    solutions: Vec<Solution>,
+    normalizations: HashMap<u8, Normalization>,
+    explorers: HashMap<u8, Explorer>,
+    synthesizers: HashMap<u8, Synthesizer>,
+    /// Read from `aoc.toml` when `main` starts
+    config: Config,
+}
+
+impl Runner {
+    /// The unnamed solution of a part is its default; the others are variants that only run with `--variants`. A part
+    /// with only named solutions defaults to the first one registered.
+    pub fn new(year: u32, mut solutions: Vec<Solution>) -> Runner {
+        solutions.sort_by_key(|s| (s.day, s.part, s.name.is_some()));
+
+        Runner {
+            year,
+            solutions,
+            normalizations: HashMap::new(),
+            explorers: HashMap::new(),
+            synthesizers: HashMap::new(),
+            config: Config::default(),
+        }
+    }
+
+    /// Overrides how the input of `day` is normalized, for puzzles where whitespace matters; the day's settings in
+    /// `aoc.toml` override this in turn
+    pub fn normalization(mut self, day: u8, normalization: Normalization) -> Runner {
+        self.normalizations.insert(day, normalization);
+        self
+    }
+
+    /// Lets `--repl` query the input of `day` as parsed by `explorer`
+    pub fn explore(mut self, day: u8, explorer: Explorer) -> Runner {
+        self.explorers.insert(day, explorer);
+        self
+    }
+
+    /// Makes `--synthesize` use `synthesizer` for `day` instead of `synthetic::resample`, which knows nothing of the
+    /// invariants of the day's input
+    pub fn synthesizer(mut self, day: u8, synthesizer: Synthesizer) -> Runner {
+        self.synthesizers.insert(day, synthesizer);
+        self
+    }
+
+    fn normalize(&self, day: u8, input: &str) -> ArcStr {
+        let normalization = self.normalizations.get(&day).copied().unwrap_or_default();
+        let normalization = self.config.day(day).normalization(normalization);
+
+        ArcStr::from(&normalization.apply(input))
+    }
+
+    fn is_default(&self, solution: &Solution) -> bool {
+        self.solutions
+            .iter()
+            .find(|s| s.day == solution.day && s.part == solution.part)
+            .is_some_and(|s| s.name == solution.name)
+    }
+
+    fn selected_solutions(&self, args: &Args) -> impl Iterator<Item = &Solution> + use<'_> {
+        let (day, part, is_variants) = (args.day, args.part, args.variants);
+
+        self.solutions.iter().filter(move |s| {
+            day.is_none_or(|day| s.day == day)
+                && part.is_none_or(|part| s.part == part)
+                && (is_variants || self.is_default(s))
+        })
+    }
+
+    /// Every selected solution on the puzzle input of each of `profiles`, `None` being the default profile
+    fn input_jobs(&self, args: &Args, profiles: &[Option<String>]) -> Vec<Job<'_>> {
+        let mut inputs: HashMap<(Option<&str>, u8), Result<ArcStr, String>> = HashMap::new();
+        let mut accepted_answers: HashMap<(Option<&str>, u8), AcceptedAnswers> = HashMap::new();
+
+        let mut jobs = Vec::new();
+
+        for solution in self.selected_solutions(args) {
+            for profile in profiles.iter().map(Option::as_deref) {
+                let input = inputs.entry((profile, solution.day)).or_insert_with(|| {
+                    self.read_input(profile, solution.day)
+                        .map(|input| self.normalize(solution.day, &input))
+                });
+
+                // answers the website accepted turn every run into a regression check
+                let day_accepted_answers = accepted_answers
+                    .entry((profile, solution.day))
+                    .or_insert_with(|| {
+                        AcceptedAnswers::load(
+                            &profile::answers_dir(&self.config, profile),
+                            solution.day,
+                        )
+                        .unwrap_or_else(|e| {
+                            eprintln!("{e}");
+                            AcceptedAnswers::default()
+                        })
+                    });
+
+                jobs.push(Job {
+                    solution,
+                    fixture: None,
+                    profile: profile.map(String::from),
+                    input: input.clone(),
+                    expected: day_accepted_answers.get(solution.part).map(String::from),
+                });
+            }
+        }
+
+        jobs
+    }
+
+    /// Every selected solution on `input`, from `--input` or `--stdin`, whose answers are unknown
+    fn given_input_jobs(&self, args: &Args, input: &str) -> Vec<Job<'_>> {
+        self.selected_solutions(args)
+            .map(|solution| Job {
+                solution,
+                fixture: None,
+                profile: None,
+                input: Ok(self.normalize(solution.day, input)),
+                expected: None,
+            })
+            .collect()
+    }
+
+    fn fixture_jobs(&self, args: &Args) -> Vec<Job<'_>> {
+        let mut fixtures: HashMap<u8, Result<Vec<Fixture>, String>> = HashMap::new();
+
+        let mut jobs = Vec::new();
+
+        for solution in self.selected_solutions(args) {
+            let day_fixtures = fixtures
+                .entry(solution.day)
+                .or_insert_with(|| Fixture::load_dir(&fixture::day_dir(solution.day)));
+
+            match day_fixtures {
+                Ok(day_fixtures) => {
+                    for fixture in day_fixtures
+                        .iter()
+                        .filter(|fixture| fixture.answers.contains_key(&solution.part))
+                    {
+                        jobs.push(Job {
+                            solution,
+                            fixture: Some(fixture.name.clone()),
+                            profile: None,
+                            input: Ok(self.normalize(solution.day, &fixture.input)),
+                            expected: fixture.answers.get(&solution.part).cloned(),
+                        });
+                    }
+                }
+                Err(e) => jobs.push(Job {
+                    solution,
+                    fixture: None,
+                    profile: None,
+                    input: Err(e.clone()),
+                    expected: None,
+                }),
+            }
+        }
+
+        jobs
+    }
+
+    fn read_input(&self, profile: Option<&str>, day: u8) -> Result<String, String> {
+        let path = profile::input_path(&self.config, profile, self.year, day);
+
+        match fs::read_to_string(&path) {
+            Ok(input) => Ok(input),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                Err(format!("{MISSING_INPUT} {}", path.display()))
+            }
+            Err(e) => Err(format!("failed to read {}: {e}", path.display())),
+        }
+    }
+
+    /// The input `--input`, `--profile` or otherwise the puzzle input gives `day`, for the commands that take one input
+    fn day_input(&self, args: &Args, day: u8) -> Result<String, String> {
+        match &args.input {
+            Some(path) => read_file(path),
+            None => self.read_input(args.profile.as_deref(), day),
+        }
+    }
+
+    fn default_solutions(&self, day: u8) -> Vec<Solution> {
+        self.solutions
+            .iter()
+            .filter(|s| s.day == day && self.is_default(s))
+            .copied()
+            .collect()
+    }
+
+    fn repl(&self, args: &Args, day: u8) -> Result<(), String> {
+        let input = if args.examples {
+            Fixture::load_dir(&fixture::day_dir(day))?
+                .into_iter()
+                .next()
+                .map(|fixture| fixture.input)
+                .ok_or_else(|| format!("day {day} has no fixtures"))?
+        } else {
+            self.day_input(args, day)?
+        };
+
+        let solutions = self.default_solutions(day);
+
+        let session = Session::new(
+            day,
+            self.normalize(day, &input),
+            self.explorers.get(&day).copied(),
+            &solutions,
+        );
+
+        println!("Exploring day {day}; type help for the commands");
+
+        session
+            .run_commands(io::stdin().lock(), io::stdout())
+            .map_err(|e| e.to_string())
+    }
+
+    fn synthesize(&self, args: &Args, day: u8) -> Result<(), String> {
+        let path = fixture::day_dir(day).join("synthetic.toml");
+
+        if path.exists() && !args.force {
+            return Err(format!(
+                "{} already exists; --force overwrites it",
+                path.display()
+            ));
+        }
+
+        let input = self.normalize(day, &self.day_input(args, day)?);
+        let synthesizer = self
+            .synthesizers
+            .get(&day)
+            .copied()
+            .unwrap_or(synthetic::resample);
+
+        let input = synthesizer(Borrow::<str>::borrow(&input), args.seed)
+            .map_err(|e| format!("failed to synthesize an input for day {day}: {e}"))?;
+
+        let (fixture, errors) =
+            synthetic::to_fixture("synthetic", input, &self.default_solutions(day));
+
+        for e in errors {
+            eprintln!("{e}\nso the fixture has no answer for that part");
+        }
+
+        fs::create_dir_all(fixture::day_dir(day)).map_err(|e| e.to_string())?;
+        fs::write(&path, fixture.to_toml())
+            .map_err(|e| format!("failed to write {}: {e}", path.display()))?;
+
+        let mut answers: Vec<String> = fixture
+            .answers
+            .iter()
+            .map(|(part, answer)| format!("part {part} = {answer}"))
+            .collect();
+        answers.sort();
+
+        println!("wrote {} ({})", path.display(), answers.join(", "));
+
+        Ok(())
+    }
+
+    fn jobs(&self, args: &Args) -> Result<Vec<Job<'_>>, String> {
+        if args.examples {
+            return Ok(self.fixture_jobs(args));
+        }
+
+        if args.stdin {
+            let input = io::read_to_string(io::stdin())
+                .map_err(|e| format!("failed to read stdin: {e}"))?;
+
+            return Ok(self.given_input_jobs(args, &input));
+        }
+
+        if let Some(path) = &args.input {
+            return Ok(self.given_input_jobs(args, &read_file(path)?));
+        }
+
+        let profiles: Vec<Option<String>> = if args.all_profiles {
+            let profiles_dir = self.config.profiles_dir();
+            let names = profile::list(&profiles_dir)?;
+
+            if names.is_empty() {
+                return Err(format!(
+                    "there are no profiles in {}",
+                    profiles_dir.display()
+                ));
+            }
+
+            names.into_iter().map(Some).collect()
+        } else {
+            vec![args.profile.clone()]
+        };
+
+        let jobs = self.input_jobs(args, &profiles);
+
+        // a profile's answer that was never recorded would otherwise pass unchecked
+        if args.all_profiles {
+            for job in jobs
+                .iter()
+                .filter(|job| job.input.is_ok() && job.expected.is_none())
+            {
+                let profile = job.profile.as_deref().unwrap_or_default();
+
+                eprintln!(
+                    "{} ({profile}) has no accepted answer to check; record it with `aoc accept {} {} <answer> --profile {profile}`",
+                    job.solution, job.solution.day, job.solution.part
+                );
+            }
+        }
+
+        Ok(jobs)
+    }
+
+    fn load_config(&mut self) -> Result<(), String> {
+        self.config = Config::load(Path::new(""))?;
+
+        match self.config.year {
+            Some(year) if u32::from(year) != self.year => Err(format!(
+                "{CONFIG_FILE} is for {year}, but this runner is for {}",
+                self.year
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn main(mut self) -> ExitCode {
+        let args = Args::parse();
+
+        if let Err(e) = self.load_config() {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+
+        if let Err(e) = trace::init(args.verbose, &args.trace, args.trace_file.as_deref()) {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+
+        if args.memory
+            && let Err(e) = alloc::enable()
+        {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+
+        if args.cache || self.config.cache {
+            cache::enable();
+        }
+
+        let command = match args.day {
+            Some(day) if args.repl => Some(self.repl(&args, day)),
+            Some(day) if args.synthesize => Some(self.synthesize(&args, day)),
+            _ => None,
+        };
+
+        if let Some(result) = command {
+            return match result {
+                Ok(()) => ExitCode::SUCCESS,
+                Err(e) => {
+                    eprintln!("{e}");
+                    ExitCode::FAILURE
+                }
+            };
+        }
+
+        if !args.json {
+            println!("Advent of code {}", self.year);
+        }
+
+        let mut is_success = true;
+        let mut reports = Vec::new();
+
+        let mut on_report = |report: SolutionReport| {
+            is_success &= report.is_success();
+
+            if args.json {
+                println!("{}", serde_json::to_string(&report).unwrap());
+            } else if report.is_success() {
+                println!("{report}");
+            } else {
+                eprintln!("{report}");
+            }
+
+            if args.variants {
+                reports.push(report);
+            }
+        };
+
+        let jobs = match self.jobs(&args) {
+            Ok(jobs) => jobs,
+            Err(e) => {
+                eprintln!("{e}");
+                return ExitCode::FAILURE;
+            }
+        };
+
+        let num_workers = match args.jobs {
+            Some(num_workers) => num_workers.get(),
+            None if args.all => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            None => 1,
+        };
+
+        if args.isolated_timing {
+            run_jobs_isolated(&jobs, num_workers, &mut on_report);
+        } else {
+            run_jobs(&jobs, num_workers, &mut on_report);
+        }
+
+        // a disagreement is reported even with --json, where the table would not fit in
+        for comparison in compare_variants(reports) {
+            is_success &= comparison.is_success();
+
+            if !comparison.is_success() {
+                eprintln!("{comparison}");
+            } else if !args.json {
+                println!("{comparison}");
+            }
+        }
+
+        if is_success {
+            ExitCode::SUCCESS
+        } else {
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_runner::Runner as _;
+
+    use crate::test_solution::{self, TrySolve};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_day() {
+        assert_eq!(parse_day("3"), Ok(3));
+        assert_eq!(parse_day("day25"), Ok(25));
+        assert!(parse_day("day0").is_err());
+        assert!(parse_day("26").is_err());
+        assert!(parse_day("dayx").is_err());
+    }
+
+    // sleeps for as many milliseconds as its input says, then answers with them
+    struct Sleep(u64);
+
+    impl aoc_runner::Runner for Sleep {
+        fn r#gen(input: ArcStr) -> Self {
+            Sleep(Borrow::<str>::borrow(&input).parse().unwrap())
+        }
+
+        fn run(&self) -> Box<dyn fmt::Display> {
+            thread::sleep(std::time::Duration::from_millis(self.0));
+            Box::new(self.0)
+        }
+
+        fn bench(&self, _: fn(&dyn fmt::Display)) {}
+    }
+
+    fn sleep_factory(input: ArcStr) -> Result<Box<dyn aoc_runner::Runner>, Box<dyn Error>> {
+        Ok(Box::new(Sleep::r#gen(input)))
+    }
+
+    #[test]
+    fn test_run_jobs_in_order() {
+        let solution = Solution {
+            day: 1,
+            part: 1,
+            name: None,
+            factory: sleep_factory,
+        };
+        let jobs: Vec<Job> = ["60", "0", "30", "x"]
+            .into_iter()
+            .map(|input| Job {
+                solution: &solution,
+                fixture: None,
+                profile: None,
+                input: Ok(ArcStr::from(input)),
+                expected: None,
+            })
+            .collect();
+
+        for num_workers in [1, 4] {
+            let mut answers = Vec::new();
+
+            run_jobs_isolated(&jobs, num_workers, &mut |report| {
+                answers.push(report.answer().map(String::from));
+            });
+
+            assert_eq!(
+                answers,
+                [Some("60".into()), Some("0".into()), Some("30".into()), None]
+            );
+        }
+    }
+
+    #[test]
+    fn test_selected_solutions_default_and_variants() {
+        let solution = |day, part, name| Solution {
+            day,
+            part,
+            name,
+            factory: sleep_factory,
+        };
+        let runner = Runner::new(
+            2024,
+            vec![
+                solution(2, 1, Some("BruteForce")),
+                solution(2, 1, None),
+                solution(2, 2, Some("Streaming")),
+                solution(2, 2, Some("BruteForce")),
+            ],
+        );
+        let selected = |args: &[&str]| -> Vec<String> {
+            let args = Args::parse_from([&["run"], args].concat());
+
+            runner
+                .selected_solutions(&args)
+                .map(|s| s.to_string())
+                .collect()
+        };
+
+        assert_eq!(
+            selected(&[]),
+            ["Day 2 - Part 1", "Day 2 - Part 2 - Streaming"]
+        );
+        assert_eq!(
+            selected(&["--variants", "2", "1"]),
+            ["Day 2 - Part 1", "Day 2 - Part 1 - BruteForce"]
+        );
+    }
+
+    #[test]
+    fn test_input_jobs_per_profile() {
+        let solution = |part| Solution {
+            day: 2,
+            part,
+            name: None,
+            factory: sleep_factory,
+        };
+        let runner = Runner::new(2024, vec![solution(1), solution(2)]);
+        let args = Args::parse_from(["run", "2"]);
+
+        let jobs = runner.input_jobs(&args, &[Some(String::from("work")), None]);
+        let jobs: Vec<(String, Option<&str>, Option<String>)> = jobs
+            .iter()
+            .map(|job| {
+                (
+                    job.solution.to_string(),
+                    job.profile.as_deref(),
+                    job.input.clone().err(),
+                )
+            })
+            .collect();
+
+        assert_eq!(jobs.len(), 4);
+        assert_eq!(jobs[0].0, "Day 2 - Part 1");
+        assert_eq!(jobs[0].1, Some("work"));
+        assert!(
+            jobs[0]
+                .2
+                .as_ref()
+                .unwrap()
+                .starts_with("no puzzle input at profiles/work/input/2024/day2.txt")
+        );
+        assert_eq!((jobs[1].0.as_str(), jobs[1].1), ("Day 2 - Part 1", None));
+        assert!(
+            jobs[1]
+                .2
+                .as_ref()
+                .unwrap()
+                .starts_with("no puzzle input at input/2024/day2.txt")
+        );
+        assert_eq!((jobs[3].0.as_str(), jobs[3].1), ("Day 2 - Part 2", None));
+
+        let jobs = runner.given_input_jobs(&args, "30\r\n");
+        assert_eq!(jobs.len(), 2);
+        assert!(
+            jobs.iter()
+                .all(|job| { job.input.as_ref().map(Borrow::<str>::borrow) == Ok("30") })
+        );
+        assert!(jobs.iter().all(|job| job.expected.is_none()));
+
+        assert!(Args::try_parse_from(["run", "--input", "day2.txt"]).is_err());
+        assert!(Args::try_parse_from(["run", "2", "--stdin", "--profile", "work"]).is_err());
+        assert!(Args::try_parse_from(["run", "--all-profiles", "--examples"]).is_err());
+        assert!(Args::try_parse_from(["run", "--all-profiles", "--variants"]).is_ok());
+    }
+
+    #[test]
+    fn test_compare_variants() {
+        let report = |name: Option<&str>, fixture: Option<&str>, answer: Result<&str, &str>| {
+            SolutionReport {
+                day: 2,
+                part: 1,
+                name: name.map(String::from),
+                fixture: fixture.map(String::from),
+                profile: None,
+                expected: None,
+                output: answer
+                    .map(|answer| {
+                        SolutionOutput::new(
+                            answer.to_string(),
+                            std::time::Duration::from_millis(1),
+                            std::time::Duration::from_millis(1),
+                        )
+                    })
+                    .map_err(String::from),
+            }
+        };
+
+        let comparisons = compare_variants(vec![
+            report(None, None, Ok("4")),
+            report(None, Some("example"), Ok("2")),
+            report(Some("BruteForce"), None, Ok("4")),
+            report(Some("BruteForce"), Some("example"), Ok("3")),
+            report(Some("Streaming"), Some("example"), Err("PANICKED")),
+            report(None, Some("other"), Err("PANICKED")),
+            report(Some("BruteForce"), Some("other"), Ok("1")),
+        ]);
+
+        assert_eq!(comparisons.len(), 2);
+        assert!(comparisons[0].is_success());
+        assert!(
+            comparisons[0]
+                .to_string()
+                .starts_with("Day 2 - Part 1: 2 variants agree on 4\n\t(default)   ")
+        );
+
+        assert!(!comparisons[1].is_success());
+        assert_eq!(
+            comparisons[1]
+                .disagreements()
+                .map(|report| report.name.as_deref())
+                .collect::<Vec<_>>(),
+            [Some("BruteForce"), Some("Streaming")]
+        );
+        assert!(comparisons[1].to_string().contains("answered 3"));
+    }
+
+    // adds up the bytes of its input in a u8
+    struct ByteSum(ArcStr);
+
+    impl TrySolve for ByteSum {
+        fn new(input: ArcStr) -> Self {
+            ByteSum(input)
+        }
+
+        fn solve(&self) -> Result<Box<dyn fmt::Display>, Box<dyn Error>> {
+            let bytes: &[u8] = self.0.borrow();
+
+            Ok(Box::new(answer::checked_sum(bytes.iter().copied())?))
+        }
+    }
+
+    #[test]
+    fn test_overflow_names_day_and_part() {
+        let solution = Solution {
+            day: 3,
+            part: 2,
+            name: None,
+            factory: test_solution::factory::<ByteSum>,
+        };
+
+        assert_eq!(
+            run(&solution, ArcStr::from("ab")).map(|output| output.answer),
+            Ok(String::from("195"))
+        );
+        assert_eq!(
+            run(&solution, ArcStr::from("abc")),
+            Err(String::from(
+                "OVERFLOWED while running: Day 3 - Part 2: u8 overflowed adding 99 to 195"
+            ))
+        );
+
+        let overflow = answer::checked_mul(u8::MAX, 2).unwrap_err();
+
+        for e in [
+            Box::new(overflow.clone()) as Box<dyn Error>,
+            Box::new(answer::SolutionError::from(overflow.clone())),
+            Box::new(io::Error::other(overflow.clone())),
+        ] {
+            assert_eq!(find_overflow(e.as_ref()), Some(&overflow));
+        }
+
+        assert_eq!(find_overflow(&io::Error::other("not a number")), None);
+    }
+
+    #[test]
+    fn test_normalize_per_day() {
+        let runner = Runner::new(2024, Vec::new()).normalization(6, Normalization::NONE);
+        let input = "\u{feff}ab\r\ncd\r\n \r\n";
+        let normalize = |day| -> String {
+            let input = runner.normalize(day, input);
+            Borrow::<str>::borrow(&input).to_string()
+        };
+
+        assert_eq!(normalize(5), "ab\ncd");
+        // ArcStr itself always trims the trailing newline
+        assert_eq!(normalize(6), "\u{feff}ab\r\ncd\r\n \r");
+    }
+}
@@ -0,0 +1,696 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
+
+use crate::{
+    answers::AcceptedAnswers, config::Config, report::SolutionReport, runner::MISSING_INPUT,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    /// Has an accepted answer that the solution, if it ran, still gives
+    Solved,
+    /// Has a solution but no accepted answer yet
+    Unverified,
+    /// Gives a different answer than the accepted one
+    Wrong,
+    /// Fails or panics
+    Failing,
+    /// Only returns a hard-coded value
+    Stub,
+    Missing,
+}
+
+impl PartStatus {
+    fn symbol(&self) -> char {
+        match self {
+            PartStatus::Solved => '*',
+            PartStatus::Unverified => '+',
+            PartStatus::Wrong | PartStatus::Failing => 'x',
+            PartStatus::Stub => 's',
+            PartStatus::Missing => '.',
+        }
+    }
+
+    fn css_class(&self) -> &'static str {
+        match self {
+            PartStatus::Solved => "solved",
+            PartStatus::Unverified => "unverified",
+            PartStatus::Wrong | PartStatus::Failing => "failing",
+            PartStatus::Stub => "stub",
+            PartStatus::Missing => "missing",
+        }
+    }
+}
+
+const LEGEND: &str = "* solved  + no accepted answer yet  x wrong or failing  s stub  . missing";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    pub status: PartStatus,
+    pub answer: Option<String>,
+    pub accepted: Option<String>,
+    pub error: Option<String>,
+    pub time: Option<Duration>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayReport {
+    pub day: u8,
+    pub parts: [PartReport; 2],
+}
+
+impl DayReport {
+    fn time(&self) -> Option<Duration> {
+        self.parts
+            .iter()
+            .filter_map(|part| part.time)
+            .reduce(|a, b| a + b)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct YearReport {
+    pub year: u16,
+    pub language: &'static str,
+    pub days: Vec<DayReport>,
+    /// Why the solutions were not run, if they were not
+    pub note: Option<String>,
+}
+
+impl YearReport {
+    pub fn num_stars(&self) -> usize {
+        self.days
+            .iter()
+            .flat_map(|day| &day.parts)
+            .filter(|part| part.status == PartStatus::Solved)
+            .count()
+    }
+
+    fn day(&self, day: u8) -> Option<&DayReport> {
+        self.days.iter().find(|day_report| day_report.day == day)
+    }
+}
+
+/// A `#[aoc(dayN, partM[, Name])]` function found in a year's sources
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegisteredSolution {
+    pub day: u8,
+    pub part: u8,
+    pub name: Option<String>,
+    pub is_stub: bool,
+}
+
+fn parse_aoc_attribute(line: &str) -> Option<(u8, u8, Option<String>)> {
+    let args = line.trim().strip_prefix("#[aoc(")?.strip_suffix(")]")?;
+    let mut args = args.split(',').map(str::trim);
+
+    let day = args.next()?.strip_prefix("day")?.parse().ok()?;
+    let part = args.next()?.strip_prefix("part")?.parse().ok()?;
+    let name = args.next().map(String::from);
+
+    Some((day, part, name))
+}
+
+// the body of the first function after `offset`, without its braces
+fn function_body(source: &str, offset: usize) -> Option<&str> {
+    let body_start = offset + source[offset..].find('{')? + 1;
+    let mut depth = 1;
+
+    for (i, c) in source[body_start..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+
+                if depth == 0 {
+                    return Some(&source[body_start..body_start + i]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+fn is_literal(expression: &str) -> bool {
+    let expression = ["Ok(", "Some("]
+        .iter()
+        .find_map(|wrapper| expression.strip_prefix(wrapper)?.strip_suffix(')'))
+        .unwrap_or(expression);
+
+    expression.parse::<i128>().is_ok()
+        || expression.parse::<f64>().is_ok()
+        || (expression.starts_with('"') && expression.ends_with('"'))
+        || expression == "true"
+        || expression == "false"
+}
+
+/// Whether a function body only returns a hard-coded value or bails out with `todo!()` and the like, ignoring the
+/// input entirely
+pub fn is_stub_body(body: &str) -> bool {
+    let code: String = body
+        .lines()
+        .map(|line| line.split("//").next().unwrap())
+        .collect::<String>()
+        .split_whitespace()
+        .collect();
+
+    let expression = code.strip_suffix(';').unwrap_or(&code);
+    let expression = expression.strip_prefix("return").unwrap_or(expression);
+
+    is_literal(expression)
+        || ["todo!(", "unimplemented!(", "panic!("]
+            .iter()
+            .any(|bail| expression.starts_with(bail) && expression.ends_with(')'))
+}
+
+pub fn scan_source(source: &str) -> Vec<RegisteredSolution> {
+    let mut solutions = Vec::new();
+    let mut offset = 0;
+
+    for line in source.split_inclusive('\n') {
+        offset += line.len();
+
+        if let Some((day, part, name)) = parse_aoc_attribute(line) {
+            let is_stub = function_body(source, offset).is_some_and(is_stub_body);
+
+            solutions.push(RegisteredSolution {
+                day,
+                part,
+                name,
+                is_stub,
+            });
+        }
+    }
+
+    solutions
+}
+
+fn scan_sources(src_dir: &Path) -> Result<Vec<RegisteredSolution>, String> {
+    let entries =
+        fs::read_dir(src_dir).map_err(|e| format!("failed to read {}: {e}", src_dir.display()))?;
+
+    let mut solutions = Vec::new();
+
+    for path in entries.filter_map(|entry| entry.ok().map(|entry| entry.path())) {
+        if path.extension().is_some_and(|extension| extension == "rs") {
+            let source = fs::read_to_string(&path)
+                .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+
+            solutions.extend(scan_source(&source));
+        }
+    }
+
+    Ok(solutions)
+}
+
+fn run_year(year_dir: &Path) -> Result<Vec<SolutionReport>, String> {
+    let output = Command::new("cargo")
+        .args(["run", "--release", "--quiet", "--", "--json"])
+        .current_dir(year_dir)
+        .output()
+        .map_err(|e| format!("failed to run cargo: {e}"))?;
+
+    let reports: Vec<SolutionReport> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+
+    if reports.is_empty() && !output.status.success() {
+        return Err(format!("cargo run failed ({})", output.status));
+    }
+
+    Ok(reports)
+}
+
+fn is_missing_input(error: &str) -> bool {
+    error.starts_with(MISSING_INPUT)
+}
+
+fn part_report(
+    solution: Option<&RegisteredSolution>,
+    report: Option<&SolutionReport>,
+    accepted: Option<&str>,
+) -> PartReport {
+    let mut part_report = PartReport {
+        status: PartStatus::Missing,
+        answer: None,
+        accepted: accepted.map(String::from),
+        error: None,
+        time: None,
+    };
+
+    match report.map(|report| &report.output) {
+        Some(Ok(output)) => {
+            part_report.answer = Some(output.answer.clone());
+            part_report.time = Some(output.generator_time + output.runner_time);
+        }
+        Some(Err(e)) if !is_missing_input(e) => part_report.error = Some(e.clone()),
+        _ => {}
+    }
+
+    part_report.status = match (solution, accepted) {
+        (Some(solution), _) if solution.is_stub => PartStatus::Stub,
+        (None, Some(_)) => PartStatus::Solved,
+        (None, None) => PartStatus::Missing,
+        (Some(_), _) if part_report.error.is_some() => PartStatus::Failing,
+        (Some(_), Some(accepted)) => match &part_report.answer {
+            Some(answer) if answer != accepted => PartStatus::Wrong,
+            _ => PartStatus::Solved,
+        },
+        (Some(_), None) => PartStatus::Unverified,
+    };
+
+    part_report
+}
+
+/// A Rust year: its registered solutions, and when `is_run` their answers and timings on the puzzle inputs
+pub fn inspect_rust_year(year: u16, year_dir: &Path, is_run: bool) -> Result<YearReport, String> {
+    let config = Config::load(year_dir)?;
+    let solutions = scan_sources(&year_dir.join("src"))?;
+
+    let (reports, note) = if is_run {
+        match run_year(year_dir) {
+            Ok(reports) => (reports, None),
+            Err(e) => (Vec::new(), Some(format!("not run: {e}"))),
+        }
+    } else {
+        (Vec::new(), Some(String::from("not run")))
+    };
+
+    let mut days: Vec<u8> = solutions.iter().map(|solution| solution.day).collect();
+    days.sort();
+    days.dedup();
+
+    let days = days
+        .into_iter()
+        .map(|day| {
+            let accepted = AcceptedAnswers::load(&config.answers_dir, day)?;
+
+            let parts = [1, 2].map(|part| {
+                // named variants are alternatives; the unnamed one is what the day is judged by
+                let solution = solutions
+                    .iter()
+                    .filter(|s| s.day == day && s.part == part)
+                    .min_by_key(|s| s.name.is_some());
+
+                let report = reports.iter().find(|r| {
+                    r.day == day
+                        && r.part == part
+                        && r.name == solution.and_then(|s| s.name.clone())
+                });
+
+                part_report(solution, report, accepted.get(part))
+            });
+
+            Ok(DayReport { day, parts })
+        })
+        .collect::<Result<Vec<DayReport>, String>>()?;
+
+    Ok(YearReport {
+        year,
+        language: "Rust",
+        days,
+        note,
+    })
+}
+
+/// A TypeScript year, where every day is a `<day>/index.ts` script printing both answers; those are not run, so only
+/// accepted answers count as stars
+pub fn inspect_typescript_year(year: u16, year_dir: &Path) -> Result<YearReport, String> {
+    let config = Config::load(year_dir)?;
+    let mut days = Vec::new();
+
+    for day in 1..=25 {
+        let has_solution = year_dir.join(day.to_string()).join("index.ts").exists();
+        let accepted = AcceptedAnswers::load(&config.answers_dir, day)?;
+
+        if !has_solution && accepted.answers.is_empty() {
+            continue;
+        }
+
+        let parts = [1, 2].map(|part| PartReport {
+            status: match (accepted.get(part), has_solution) {
+                (Some(_), _) => PartStatus::Solved,
+                (None, true) => PartStatus::Unverified,
+                (None, false) => PartStatus::Missing,
+            },
+            answer: accepted.get(part).map(String::from),
+            accepted: accepted.get(part).map(String::from),
+            error: None,
+            time: None,
+        });
+
+        days.push(DayReport { day, parts });
+    }
+
+    Ok(YearReport {
+        year,
+        language: "TypeScript",
+        days,
+        note: None,
+    })
+}
+
+/// Every year directory (named like `2024`) under `root`, oldest first
+pub fn inspect_all(root: &Path, is_run: bool) -> Result<Vec<YearReport>, String> {
+    let entries =
+        fs::read_dir(root).map_err(|e| format!("failed to read {}: {e}", root.display()))?;
+
+    let mut year_dirs: Vec<(u16, PathBuf)> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir())
+        .filter_map(|path| {
+            let year = path.file_name()?.to_str()?.parse::<u16>().ok()?;
+            Some((year, path))
+        })
+        .collect();
+    year_dirs.sort();
+
+    year_dirs
+        .iter()
+        .map(|(year, year_dir)| {
+            if year_dir.join("Cargo.toml").exists() {
+                inspect_rust_year(*year, year_dir, is_run)
+            } else {
+                inspect_typescript_year(*year, year_dir)
+            }
+        })
+        .collect()
+}
+
+fn format_time(time: Option<Duration>) -> String {
+    time.map(|time| format!("{time:.1?}")).unwrap_or_default()
+}
+
+fn describe_part(part: &PartReport) -> String {
+    let detail = match (&part.answer, &part.error) {
+        (_, Some(e)) => e.lines().next().unwrap_or_default().to_string(),
+        (Some(answer), None) if part.status == PartStatus::Wrong => format!(
+            "{answer} (accepted {})",
+            part.accepted.as_deref().unwrap_or_default()
+        ),
+        (Some(answer), None) => answer.clone(),
+        (None, None) => String::new(),
+    };
+
+    format!("{} {detail}", part.status.symbol())
+}
+
+pub fn render_text(years: &[YearReport]) -> String {
+    let mut text = String::new();
+
+    let header: String = (1..=25).map(|day| format!("{day:>3}")).collect();
+
+    for year in years {
+        text += &format!(
+            "{} ({}): {} stars{}\n",
+            year.year,
+            year.language,
+            year.num_stars(),
+            year.note
+                .as_ref()
+                .map(|note| format!(", {note}"))
+                .unwrap_or_default()
+        );
+
+        text += &format!("     {header}\n     ");
+
+        for day in 1..=25 {
+            let symbols: String = match year.day(day) {
+                Some(day) => day.parts.iter().map(|part| part.status.symbol()).collect(),
+                None => String::from(".."),
+            };
+
+            text += &format!(" {symbols}");
+        }
+
+        text += "\n\n";
+
+        for day in &year.days {
+            let line = format!(
+                "  day {:>2}  {:<32} {:<32} {}",
+                day.day,
+                describe_part(&day.parts[0]),
+                describe_part(&day.parts[1]),
+                format_time(day.time())
+            );
+
+            text += line.trim_end();
+            text += "\n";
+        }
+
+        text += "\n";
+    }
+
+    text + LEGEND + "\n"
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub fn render_html(years: &[YearReport]) -> String {
+    let mut html = String::from(
+        "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>Advent of Code progress</title>
+<style>
+body { background: #0f0f23; color: #cccccc; font-family: monospace; }
+table { border-collapse: collapse; margin-bottom: 1em; }
+td, th { padding: 2px 6px; text-align: left; }
+.solved { color: #ffff66; }
+.unverified { color: #9999cc; }
+.failing { color: #ff6666; }
+.stub { color: #ff9933; }
+.missing { color: #333340; }
+</style>
+</head>
+<body>
+<h1>Advent of Code progress</h1>
+",
+    );
+
+    for year in years {
+        html += &format!(
+            "<h2>{} ({}): {} stars</h2>\n",
+            year.year,
+            year.language,
+            year.num_stars()
+        );
+
+        if let Some(note) = &year.note {
+            html += &format!("<p>{}</p>\n", escape_html(note));
+        }
+
+        html += "<table>\n<tr>";
+
+        for day in 1..=25 {
+            html += &format!("<th>{day}</th>");
+        }
+
+        html += "</tr>\n<tr>";
+
+        for day in 1..=25 {
+            html += "<td>";
+
+            for part_i in 0..2 {
+                let status = year
+                    .day(day)
+                    .map(|day| day.parts[part_i].status)
+                    .unwrap_or(PartStatus::Missing);
+
+                html += &format!(
+                    "<span class=\"{}\">{}</span>",
+                    status.css_class(),
+                    status.symbol()
+                );
+            }
+
+            html += "</td>";
+        }
+
+        html += "</tr>\n</table>\n<table>\n<tr><th>day</th><th>part 1</th><th>part 2</th><th>time</th></tr>\n";
+
+        for day in &year.days {
+            html += &format!("<tr><td>{}</td>", day.day);
+
+            for part in &day.parts {
+                html += &format!(
+                    "<td class=\"{}\">{}</td>",
+                    part.status.css_class(),
+                    escape_html(&describe_part(part))
+                );
+            }
+
+            html += &format!("<td>{}</td></tr>\n", format_time(day.time()));
+        }
+
+        html += "</table>\n";
+    }
+
+    html + &format!("<p>{}</p>\n</body>\n</html>\n", escape_html(LEGEND))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::SolutionOutput;
+
+    const SOURCE: &str = r#"
+#[aoc(day6, part1)]
+fn part1(input: &str) -> usize {
+    let (map, guard) = parse(input);
+    walk(map, guard).len()
+}
+
+#[aoc(day6, part2)]
+fn part2(input: &str) -> usize {
+    return 123;
+}
+
+#[aoc(day3, part2, Streaming)]
+fn part2_streaming(input: &[u8]) -> io::Result<u32> {
+    todo!() // later
+}
+"#;
+
+    #[test]
+    fn test_scan_source() {
+        let solution = |day, part, name: Option<&str>, is_stub| RegisteredSolution {
+            day,
+            part,
+            name: name.map(String::from),
+            is_stub,
+        };
+
+        assert_eq!(
+            scan_source(SOURCE),
+            vec![
+                solution(6, 1, None, false),
+                solution(6, 2, None, true),
+                solution(3, 2, Some("Streaming"), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_stub_body() {
+        assert!(is_stub_body(" 0 "));
+        assert!(is_stub_body("return \"abc\";"));
+        assert!(is_stub_body("Ok(42)"));
+        assert!(is_stub_body("unimplemented!(\"part 2\")"));
+        assert!(!is_stub_body("input.len()"));
+        assert!(!is_stub_body("let x = 1;\n x"));
+    }
+
+    fn run_report(output: Result<&str, &str>) -> SolutionReport {
+        SolutionReport {
+            day: 1,
+            part: 1,
+            name: None,
+            fixture: None,
+            profile: None,
+            expected: None,
+            output: output
+                .map(|answer| {
+                    SolutionOutput::new(
+                        answer.to_string(),
+                        Duration::from_millis(1),
+                        Duration::from_millis(2),
+                    )
+                })
+                .map_err(String::from),
+        }
+    }
+
+    #[test]
+    fn test_part_status() {
+        let solution = RegisteredSolution {
+            day: 1,
+            part: 1,
+            name: None,
+            is_stub: false,
+        };
+        let stub = RegisteredSolution {
+            is_stub: true,
+            ..solution.clone()
+        };
+
+        let status = |solution, report: Option<SolutionReport>, accepted| {
+            part_report(solution, report.as_ref(), accepted).status
+        };
+
+        let ok = || Some(run_report(Ok("42")));
+
+        assert_eq!(
+            status(Some(&solution), ok(), Some("42")),
+            PartStatus::Solved
+        );
+        assert_eq!(status(Some(&solution), ok(), Some("41")), PartStatus::Wrong);
+        assert_eq!(status(Some(&solution), ok(), None), PartStatus::Unverified);
+        assert_eq!(
+            status(Some(&solution), None, Some("42")),
+            PartStatus::Solved
+        );
+        assert_eq!(status(Some(&stub), ok(), Some("42")), PartStatus::Stub);
+        assert_eq!(status(None, None, None), PartStatus::Missing);
+        assert_eq!(
+            status(Some(&solution), Some(run_report(Err("PANICKED"))), None),
+            PartStatus::Failing
+        );
+        assert_eq!(
+            status(
+                Some(&solution),
+                Some(run_report(Err("no puzzle input at input/2024/day1.txt"))),
+                None
+            ),
+            PartStatus::Unverified
+        );
+
+        assert_eq!(
+            part_report(Some(&solution), ok().as_ref(), None).time,
+            Some(Duration::from_millis(3))
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let years = vec![YearReport {
+            year: 2024,
+            language: "Rust",
+            days: vec![DayReport {
+                day: 2,
+                parts: [
+                    part_report(None, None, Some("2")),
+                    PartReport {
+                        status: PartStatus::Stub,
+                        answer: Some(String::from("<4>")),
+                        accepted: None,
+                        error: None,
+                        time: None,
+                    },
+                ],
+            }],
+            note: None,
+        }];
+
+        let text = render_text(&years);
+        assert!(text.contains("2024 (Rust): 1 stars"));
+        assert!(text.contains(" .. *s .. "));
+
+        let html = render_html(&years);
+        assert!(html.contains("<span class=\"solved\">*</span><span class=\"stub\">s</span>"));
+        assert!(html.contains("s &lt;4&gt;"));
+    }
+}
@@ -0,0 +1,216 @@
+use std::{collections::HashMap, error::Error};
+
+use aoc_runner::ArcStr;
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom};
+
+use crate::{
+    fixture::Fixture,
+    runner::{self, Solution},
+};
+
+/// Makes a stand-in for a day's real input from `input` and a seed, with the same format and roughly the same size and
+/// values, that can be shared and committed where the real input may not be
+pub type Synthesizer = fn(&str, u64) -> Result<String, Box<dyn Error>>;
+
+enum Token<'a> {
+    Number(&'a str),
+    Text(&'a str),
+}
+
+// runs of digits and whatever is between them
+fn tokenize(line: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+
+    for (i, c) in line.char_indices() {
+        let is_digit = c.is_ascii_digit();
+        let was_digit = line[start..].starts_with(|c: char| c.is_ascii_digit());
+
+        if i > start && is_digit != was_digit {
+            tokens.push(token(&line[start..i]));
+            start = i;
+        }
+    }
+
+    if start < line.len() {
+        tokens.push(token(&line[start..]));
+    }
+
+    tokens
+}
+
+fn token(text: &str) -> Token<'_> {
+    if text.starts_with(|c: char| c.is_ascii_digit()) {
+        Token::Number(text)
+    } else {
+        Token::Text(text)
+    }
+}
+
+// a row of a map or word search: no numbers and no spaces
+fn is_grid_line(line: &str) -> bool {
+    !line.is_empty() && !line.contains(|c: char| c.is_ascii_digit() || c.is_whitespace())
+}
+
+/// The synthesizer of days that have none of their own. It keeps the lines of `input` and the text between their
+/// numbers, replaces every number with one drawn from the numbers in the same position of other lines, and shuffles the
+/// characters of grid rows among all the grid rows. Invariants a puzzle relies on, like consistent rules, may not hold
+/// afterwards, which is what a day's own synthesizer is for.
+pub fn resample(input: &str, seed: u64) -> Result<String, Box<dyn Error>> {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    // numbers by their position in the line
+    let mut columns: HashMap<usize, Vec<&str>> = HashMap::new();
+    let mut grid_chars: Vec<char> = Vec::new();
+
+    for line in input.lines() {
+        if is_grid_line(line) {
+            grid_chars.extend(line.chars());
+            continue;
+        }
+
+        let numbers = tokenize(line).into_iter().filter_map(|token| match token {
+            Token::Number(number) => Some(number),
+            Token::Text(_) => None,
+        });
+
+        for (column, number) in numbers.enumerate() {
+            columns.entry(column).or_default().push(number);
+        }
+    }
+
+    grid_chars.shuffle(&mut rng);
+    let mut grid_chars = grid_chars.into_iter();
+
+    let lines: Vec<String> = input
+        .lines()
+        .map(|line| {
+            if is_grid_line(line) {
+                return grid_chars.by_ref().take(line.chars().count()).collect();
+            }
+
+            let mut column = 0;
+
+            tokenize(line)
+                .into_iter()
+                .map(|token| match token {
+                    Token::Number(_) => {
+                        let numbers = &columns[&column];
+                        column += 1;
+
+                        numbers[rng.random_range(0..numbers.len())]
+                    }
+                    Token::Text(text) => text,
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(lines.join("\n"))
+}
+
+/// `input` as a fixture named `name`, with the answers of `solutions` to it. A part whose solution fails is left
+/// without an answer, and its error is returned next to the fixture.
+pub fn to_fixture(name: &str, input: String, solutions: &[Solution]) -> (Fixture, Vec<String>) {
+    let mut answers = HashMap::new();
+    let mut errors = Vec::new();
+
+    for solution in solutions {
+        match runner::run(solution, ArcStr::from(input.as_str())) {
+            Ok(output) => {
+                answers.insert(solution.part, output.answer);
+            }
+            Err(e) => errors.push(format!("{solution}: {e}")),
+        }
+    }
+
+    let fixture = Fixture {
+        name: name.to_string(),
+        input,
+        answers,
+    };
+
+    (fixture, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Borrow, fmt};
+
+    use super::*;
+    use crate::test_solution::{self, TrySolve};
+
+    #[test]
+    fn test_resample_keeps_layout() {
+        let input = "3   4\n4   3\n2   5\n\nmul(3,x)\n#.^\n..#";
+        let synthetic = resample(input, 7).unwrap();
+
+        assert_eq!(synthetic, resample(input, 7).unwrap());
+        assert_ne!(synthetic, resample(input, 8).unwrap());
+
+        let lines: Vec<&str> = synthetic.lines().collect();
+        assert_eq!(lines.len(), 7);
+
+        for line in &lines[..3] {
+            let (left, right) = line.split_once("   ").unwrap();
+
+            assert!(["3", "4", "2"].contains(&left), "{line}");
+            assert!(["4", "3", "5"].contains(&right), "{line}");
+        }
+
+        assert_eq!(lines[3], "");
+        assert!(["mul(3,x)", "mul(4,x)", "mul(2,x)"].contains(&lines[4]));
+
+        let mut grid_chars: Vec<char> = lines[5..].concat().chars().collect();
+        grid_chars.sort();
+        assert_eq!(grid_chars, ['#', '#', '.', '.', '.', '^']);
+    }
+
+    // sums the numbers of its input, and fails on an empty one
+    struct Sum(ArcStr);
+
+    impl TrySolve for Sum {
+        fn new(input: ArcStr) -> Self {
+            Sum(input)
+        }
+
+        fn solve(&self) -> Result<Box<dyn fmt::Display>, Box<dyn Error>> {
+            let input: &str = self.0.borrow();
+
+            if input.is_empty() {
+                return Err("empty input".into());
+            }
+
+            let sum: u32 = input
+                .split_whitespace()
+                .map(|n| n.parse::<u32>())
+                .sum::<Result<_, _>>()?;
+
+            Ok(Box::new(sum))
+        }
+    }
+
+    #[test]
+    fn test_to_fixture() {
+        let solutions = [1, 2].map(|part| Solution {
+            day: 1,
+            part,
+            name: None,
+            factory: test_solution::factory::<Sum>,
+        });
+
+        let (fixture, errors) = to_fixture("synthetic", String::from("1 2\n3"), &solutions);
+
+        assert_eq!(
+            fixture.answers,
+            HashMap::from([(1, "6".into()), (2, "6".into())])
+        );
+        assert!(errors.is_empty());
+
+        let (fixture, errors) = to_fixture("synthetic", String::new(), &solutions[1..]);
+
+        assert!(fixture.answers.is_empty());
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("Day 1 - Part 2: FAILED while running"));
+    }
+}
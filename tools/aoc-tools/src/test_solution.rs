@@ -0,0 +1,39 @@
+use std::{error::Error, fmt};
+
+use aoc_runner::ArcStr;
+
+/// A part for tests that can fail, written as a constructor and a fallible `solve` instead of a whole
+/// `aoc_runner::Runner`
+pub(crate) trait TrySolve: Sized + 'static {
+    fn new(input: ArcStr) -> Self;
+
+    fn solve(&self) -> Result<Box<dyn fmt::Display>, Box<dyn Error>>;
+}
+
+struct TrySolveRunner<T>(T);
+
+impl<T: TrySolve> aoc_runner::Runner for TrySolveRunner<T> {
+    fn r#gen(input: ArcStr) -> Self {
+        TrySolveRunner(T::new(input))
+    }
+
+    // what aoc-runner's own main would call; the runner here always calls `try_run`
+    fn run(&self) -> Box<dyn fmt::Display> {
+        self.try_run().unwrap()
+    }
+
+    fn try_run(&self) -> Result<Box<dyn fmt::Display>, Box<dyn Error>> {
+        self.0.solve()
+    }
+
+    fn bench(&self, _: fn(&dyn fmt::Display)) {}
+}
+
+/// The `Solution::factory` of `T`
+pub(crate) fn factory<T: TrySolve>(
+    input: ArcStr,
+) -> Result<Box<dyn aoc_runner::Runner>, Box<dyn Error>> {
+    Ok(Box::new(<TrySolveRunner<T> as aoc_runner::Runner>::r#gen(
+        input,
+    )))
+}
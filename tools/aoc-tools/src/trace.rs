@@ -0,0 +1,50 @@
+use std::{fs::File, io, path::Path, sync::Mutex};
+
+use tracing_subscriber::{EnvFilter, filter::LevelFilter};
+
+// every solution runs inside a `solution` span with its day and part, so a day's traces can be enabled on their own
+// with a `[solution{day=N}]` directive. nothing is printed unless asked for.
+pub fn init(verbosity: u8, trace_days: &[String], trace_file: Option<&Path>) -> Result<(), String> {
+    let level = match verbosity {
+        0 => LevelFilter::OFF,
+        1 => LevelFilter::INFO,
+        2 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    };
+
+    let mut filter = EnvFilter::default().add_directive(level.into());
+
+    for trace_day in trace_days {
+        let (day, day_level) = trace_day.split_once('=').unwrap_or((trace_day, "trace"));
+
+        let day: u8 = day
+            .trim_start_matches("day")
+            .parse()
+            .map_err(|_| format!("invalid day in --trace {trace_day}"))?;
+
+        let directive = format!("[solution{{day={day}}}]={day_level}")
+            .parse()
+            .map_err(|e| format!("invalid level in --trace {trace_day}: {e}"))?;
+
+        filter = filter.add_directive(directive);
+    }
+
+    let subscriber = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_target(false);
+
+    match trace_file {
+        Some(trace_file) => {
+            let file = File::create(trace_file)
+                .map_err(|e| format!("failed to create {}: {e}", trace_file.display()))?;
+
+            subscriber
+                .with_writer(Mutex::new(file))
+                .with_ansi(false)
+                .init();
+        }
+        None => subscriber.with_writer(io::stderr).init(),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,371 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const RED: Rgb = Rgb(230, 70, 70);
+    pub const GREEN: Rgb = Rgb(80, 200, 100);
+    pub const YELLOW: Rgb = Rgb(240, 200, 60);
+    pub const BLUE: Rgb = Rgb(70, 130, 230);
+    pub const MAGENTA: Rgb = Rgb(200, 90, 200);
+    pub const CYAN: Rgb = Rgb(70, 200, 210);
+}
+
+/// One state of a character grid, with some of its cells highlighted in a colour
+#[derive(Clone, Debug, PartialEq)]
+pub struct GridFrame {
+    cells: Vec<Vec<char>>,
+    highlights: HashMap<(usize, usize), Rgb>,
+}
+
+impl GridFrame {
+    pub fn new(cells: Vec<Vec<char>>) -> GridFrame {
+        GridFrame {
+            cells,
+            highlights: HashMap::new(),
+        }
+    }
+
+    pub fn parse(input: &str) -> GridFrame {
+        GridFrame::new(input.lines().map(|line| line.chars().collect()).collect())
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.cells.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    pub fn cell(&self, row_i: usize, col_i: usize) -> Option<char> {
+        self.cells.get(row_i)?.get(col_i).copied()
+    }
+
+    pub fn highlight_of(&self, row_i: usize, col_i: usize) -> Option<Rgb> {
+        self.highlights.get(&(row_i, col_i)).copied()
+    }
+
+    /// Cells outside of the grid are ignored
+    pub fn set_cell(&mut self, row_i: usize, col_i: usize, c: char) {
+        if let Some(cell) = self.cells.get_mut(row_i).and_then(|row| row.get_mut(col_i)) {
+            *cell = c;
+        }
+    }
+
+    /// A later highlight of the same cell replaces the earlier one
+    pub fn highlight(&mut self, row_i: usize, col_i: usize, color: Rgb) {
+        self.highlights.insert((row_i, col_i), color);
+    }
+
+    pub fn clear_highlights(&mut self) {
+        self.highlights.clear();
+    }
+
+    /// The grid with highlighted cells drawn as black text on a 24-bit background colour
+    pub fn to_ansi(&self) -> String {
+        let mut ansi = String::new();
+
+        for (row_i, row) in self.cells.iter().enumerate() {
+            for (col_i, c) in row.iter().enumerate() {
+                match self.highlight_of(row_i, col_i) {
+                    Some(Rgb(r, g, b)) => {
+                        ansi += &format!("\x1b[30;48;2;{r};{g};{b}m{c}\x1b[0m");
+                    }
+                    None => ansi.push(*c),
+                }
+            }
+
+            ansi.push('\n');
+        }
+
+        ansi
+    }
+}
+
+/// How a frame is drawn into an image: every cell is a square of `cell_size` pixels, coloured by its highlight or
+/// otherwise by its character
+#[derive(Clone, Debug)]
+pub struct ImageStyle {
+    pub cell_size: usize,
+    pub colors: HashMap<char, Rgb>,
+    pub default_color: Rgb,
+}
+
+impl Default for ImageStyle {
+    fn default() -> Self {
+        ImageStyle {
+            cell_size: 4,
+            colors: HashMap::from([
+                ('.', Rgb(25, 25, 35)),
+                (' ', Rgb(25, 25, 35)),
+                ('#', Rgb(210, 210, 210)),
+            ]),
+            default_color: Rgb(110, 110, 130),
+        }
+    }
+}
+
+impl ImageStyle {
+    fn color_of(&self, frame: &GridFrame, row_i: usize, col_i: usize) -> Rgb {
+        frame.highlight_of(row_i, col_i).unwrap_or_else(|| {
+            frame
+                .cell(row_i, col_i)
+                .and_then(|c| self.colors.get(&c).copied())
+                .unwrap_or(self.default_color)
+        })
+    }
+
+    fn image_size(&self, frame: &GridFrame) -> (usize, usize) {
+        (
+            frame.num_cols() * self.cell_size,
+            frame.num_rows() * self.cell_size,
+        )
+    }
+
+    /// The colour of every pixel, row by row
+    fn pixels(&self, frame: &GridFrame) -> Vec<Rgb> {
+        let (width, height) = self.image_size(frame);
+
+        (0..height)
+            .flat_map(|y| {
+                (0..width)
+                    .map(move |x| self.color_of(frame, y / self.cell_size, x / self.cell_size))
+            })
+            .collect()
+    }
+}
+
+fn to_io_error(e: impl std::error::Error + Send + Sync + 'static) -> io::Error {
+    io::Error::other(e)
+}
+
+pub fn write_png(frame: &GridFrame, style: &ImageStyle, path: &Path) -> io::Result<()> {
+    let (width, height) = style.image_size(frame);
+
+    let mut encoder = png::Encoder::new(
+        BufWriter::new(File::create(path)?),
+        width as u32,
+        height as u32,
+    );
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let data: Vec<u8> = style
+        .pixels(frame)
+        .iter()
+        .flat_map(|Rgb(r, g, b)| [*r, *g, *b])
+        .collect();
+
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&data))
+        .map_err(to_io_error)
+}
+
+/// A sequence of grid states, e.g. one per step of a simulation
+#[derive(Clone, Debug, Default)]
+pub struct Animation {
+    frames: Vec<GridFrame>,
+}
+
+impl Animation {
+    pub fn new() -> Animation {
+        Animation { frames: Vec::new() }
+    }
+
+    pub fn push(&mut self, frame: GridFrame) {
+        self.frames.push(frame);
+    }
+
+    pub fn frames(&self) -> &[GridFrame] {
+        &self.frames
+    }
+
+    /// Redraws every frame in place on the terminal, `delay` apart
+    pub fn play(&self, delay: Duration) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+
+        for frame in &self.frames {
+            write!(stdout, "\x1b[H\x1b[2J{}", frame.to_ansi())?;
+            stdout.flush()?;
+            thread::sleep(delay);
+        }
+
+        Ok(())
+    }
+
+    /// Writes every frame to `dir` as frame_00000.png, frame_00001.png, ...
+    pub fn write_png_frames(&self, style: &ImageStyle, dir: &Path) -> io::Result<Vec<PathBuf>> {
+        fs::create_dir_all(dir)?;
+
+        self.frames
+            .iter()
+            .enumerate()
+            .map(|(frame_i, frame)| {
+                let path = dir.join(format!("frame_{frame_i:05}.png"));
+                write_png(frame, style, &path).map(|()| path)
+            })
+            .collect()
+    }
+
+    /// Writes a looping GIF. Grids use few colours, so every frame shares one exact palette instead of being
+    /// quantized; more than 256 distinct colours is an error.
+    pub fn write_gif(&self, style: &ImageStyle, delay: Duration, path: &Path) -> io::Result<()> {
+        let (width, height) = self
+            .frames
+            .iter()
+            .map(|frame| style.image_size(frame))
+            .fold((0, 0), |(width, height), (frame_width, frame_height)| {
+                (width.max(frame_width), height.max(frame_height))
+            });
+
+        let (Ok(gif_width), Ok(gif_height)) = (u16::try_from(width), u16::try_from(height)) else {
+            return Err(io::Error::other(format!(
+                "{width}x{height} is too large for a GIF; use a smaller cell size"
+            )));
+        };
+
+        let frame_pixels: Vec<Vec<Rgb>> = self
+            .frames
+            .iter()
+            .map(|frame| style.pixels(frame))
+            .collect();
+
+        let mut palette: Vec<Rgb> = Vec::new();
+        let mut palette_indices: HashMap<Rgb, u8> = HashMap::new();
+
+        for color in frame_pixels.iter().flatten() {
+            if !palette_indices.contains_key(color) {
+                let index = u8::try_from(palette.len())
+                    .map_err(|_| io::Error::other("a GIF can not have more than 256 colours"))?;
+
+                palette_indices.insert(*color, index);
+                palette.push(*color);
+            }
+        }
+
+        let global_palette: Vec<u8> = palette
+            .iter()
+            .flat_map(|Rgb(r, g, b)| [*r, *g, *b])
+            .collect();
+
+        let mut encoder = gif::Encoder::new(
+            BufWriter::new(File::create(path)?),
+            gif_width,
+            gif_height,
+            &global_palette,
+        )
+        .map_err(to_io_error)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(to_io_error)?;
+
+        for (frame, pixels) in self.frames.iter().zip(&frame_pixels) {
+            let (frame_width, frame_height) = style.image_size(frame);
+
+            let gif_frame = gif::Frame {
+                width: frame_width as u16,
+                height: frame_height as u16,
+                // in hundredths of a second
+                delay: (delay.as_millis() / 10).min(u16::MAX as u128) as u16,
+                buffer: Cow::Owned(pixels.iter().map(|color| palette_indices[color]).collect()),
+                ..gif::Frame::default()
+            };
+
+            encoder.write_frame(&gif_frame).map_err(to_io_error)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> GridFrame {
+        let mut frame = GridFrame::parse("..#\n.X.");
+        frame.highlight(1, 1, Rgb::RED);
+        frame
+    }
+
+    #[test]
+    fn test_to_ansi() {
+        assert_eq!(
+            frame().to_ansi(),
+            "..#\n.\x1b[30;48;2;230;70;70mX\x1b[0m.\n"
+        );
+    }
+
+    #[test]
+    fn test_set_cell() {
+        let mut frame = frame();
+        frame.set_cell(0, 0, '^');
+        frame.set_cell(5, 5, '^');
+
+        assert_eq!(frame.cell(0, 0), Some('^'));
+        assert_eq!(frame.cell(5, 5), None);
+    }
+
+    #[test]
+    fn test_pixels() {
+        let style = ImageStyle {
+            cell_size: 2,
+            ..ImageStyle::default()
+        };
+
+        let pixels = style.pixels(&frame());
+        let dot = style.colors[&'.'];
+        let wall = style.colors[&'#'];
+
+        assert_eq!(style.image_size(&frame()), (6, 4));
+        assert_eq!(&pixels[0..6], &[dot, dot, dot, dot, wall, wall]);
+        assert_eq!(&pixels[18..24], &[dot, dot, Rgb::RED, Rgb::RED, dot, dot]);
+    }
+
+    #[test]
+    fn test_write_gif_and_png_frames() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut animation = Animation::new();
+        animation.push(GridFrame::parse(".#\n#."));
+        animation.push(frame());
+
+        let style = ImageStyle::default();
+        let gif_path = dir.path().join("animation.gif");
+
+        animation
+            .write_gif(&style, Duration::from_millis(100), &gif_path)
+            .unwrap();
+
+        let mut decoder = gif::DecodeOptions::new()
+            .read_info(File::open(&gif_path).unwrap())
+            .unwrap();
+        let mut num_frames = 0;
+
+        while let Some(gif_frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(gif_frame.delay, 10);
+            num_frames += 1;
+        }
+
+        assert_eq!(num_frames, 2);
+
+        let paths = animation
+            .write_png_frames(&style, &dir.path().join("frames"))
+            .unwrap();
+
+        assert_eq!(paths.len(), 2);
+        assert!(paths.iter().all(|path| path.exists()));
+    }
+}
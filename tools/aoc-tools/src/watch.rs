@@ -0,0 +1,330 @@
+use std::{
+    collections::HashMap,
+    env,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::mpsc,
+    time::Duration,
+};
+
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+use crate::{config::Config, fixture, report::SolutionReport};
+
+// editors and `cargo fmt` touch a file several times per save, so events are collected until it is quiet again
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// The files of one day inside a year's crate: its source, its puzzle input and its fixtures
+struct DayPaths {
+    crate_dir: PathBuf,
+    source: PathBuf,
+    input_dir: PathBuf,
+    input_file_name: String,
+    /// The day's `input` in aoc.toml, which may be outside `input_dir`
+    input: Option<PathBuf>,
+    fixture_dir: PathBuf,
+}
+
+impl DayPaths {
+    fn new(crate_dir: &Path, config: &Config, day: u8) -> DayPaths {
+        DayPaths {
+            crate_dir: crate_dir.to_path_buf(),
+            source: crate_dir.join(format!("src/day{day}.rs")),
+            input_dir: crate_dir.join(&config.input_dir),
+            input_file_name: format!("day{day}.txt"),
+            input: config.day(day).input.map(|input| crate_dir.join(input)),
+            fixture_dir: crate_dir.join(fixture::day_dir(day)),
+        }
+    }
+
+    fn is_relevant(&self, path: &Path) -> bool {
+        path == self.source
+            || path.starts_with(&self.fixture_dir)
+            || self.input.as_deref() == Some(path)
+            || (path.starts_with(&self.input_dir)
+                && path
+                    .file_name()
+                    .is_some_and(|file_name| *file_name == *self.input_file_name))
+    }
+
+    fn roots(&self) -> Vec<(PathBuf, RecursiveMode)> {
+        let mut roots = vec![
+            // not recursive so target/ is left alone; it is only watched for input/ and fixtures/ being created
+            (self.crate_dir.clone(), RecursiveMode::NonRecursive),
+            (self.crate_dir.join("src"), RecursiveMode::NonRecursive),
+            (self.input_dir.clone(), RecursiveMode::Recursive),
+            (self.crate_dir.join("fixtures"), RecursiveMode::Recursive),
+        ];
+
+        if let Some(input_dir) = self.input.as_deref().and_then(Path::parent) {
+            roots.push((input_dir.to_path_buf(), RecursiveMode::NonRecursive));
+        }
+
+        roots
+    }
+}
+
+/// The answers of the previous run, by fixture, part and variant name
+type Answers = HashMap<(Option<String>, u8, Option<String>), Option<String>>;
+
+// release builds by default, since the run times are compared between runs and debug builds of heavy days are slow
+fn cargo_args(day: &str, examples: bool, debug: bool) -> Vec<&str> {
+    let mut args = vec!["run", "--quiet"];
+
+    if !debug {
+        args.push("--release");
+    }
+
+    args.extend(["--", day, "--json", "--variants"]);
+
+    if examples {
+        args.push("--examples");
+    }
+
+    args
+}
+
+fn run_reports(day: u8, examples: bool, debug: bool) -> Result<Vec<SolutionReport>, String> {
+    let day = day.to_string();
+    let args = cargo_args(&day, examples, debug);
+
+    // compiler errors and traces go straight to the terminal
+    let output = Command::new("cargo")
+        .args(&args)
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| format!("failed to run cargo: {e}"))?;
+
+    let reports = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| {
+            serde_json::from_str(line)
+                .map_err(|e| format!("unexpected runner output {line:?}: {e}"))
+        })
+        .collect::<Result<Vec<SolutionReport>, String>>()?;
+
+    if reports.is_empty() && !output.status.success() {
+        return Err(format!(
+            "cargo run {} failed ({})",
+            args.join(" "),
+            output.status
+        ));
+    }
+
+    Ok(reports)
+}
+
+fn describe(report: &SolutionReport, previous_answer: Option<&Option<String>>) -> String {
+    let answer = report.answer().map(String::from);
+
+    let mut notes = Vec::new();
+
+    if let Some(expected) = &report.expected {
+        notes.push(match report.is_correct() {
+            Some(true) => String::from("ok"),
+            _ => format!("WRONG, expected {expected}"),
+        });
+    }
+
+    match previous_answer {
+        Some(previous_answer) if *previous_answer != answer => notes.push(format!(
+            "was {}",
+            previous_answer.as_deref().unwrap_or("an error")
+        )),
+        Some(_) => notes.push(String::from("unchanged")),
+        None => {}
+    }
+
+    let result = match &report.output {
+        Ok(output) => output.answer.clone(),
+        Err(e) => e.clone(),
+    };
+
+    if notes.is_empty() {
+        format!("{}: {result}", report.label())
+    } else {
+        format!("{}: {result} ({})", report.label(), notes.join("; "))
+    }
+}
+
+fn run_day(day: u8, debug: bool, previous_answers: &mut Answers) {
+    for examples in [false, true] {
+        match run_reports(day, examples, debug) {
+            Ok(reports) => {
+                for report in reports {
+                    let key = (report.fixture.clone(), report.part, report.name.clone());
+                    let answer = report.answer().map(String::from);
+
+                    println!("{}", describe(&report, previous_answers.get(&key)));
+
+                    previous_answers.insert(key, answer);
+                }
+            }
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+}
+
+/// Re-runs a day's parts and examples whenever its source, input or fixtures change, printing how the answers
+/// differ from the previous run and from the fixtures' expected answers. Runs until interrupted.
+pub fn watch(config: &Config, day: u8, debug: bool) -> Result<(), String> {
+    let crate_dir = env::current_dir().map_err(|e| e.to_string())?;
+
+    if !crate_dir.join("Cargo.toml").exists() {
+        return Err(String::from(
+            "run `aoc watch` from a year's crate directory",
+        ));
+    }
+
+    let day_paths = DayPaths::new(&crate_dir, config, day);
+
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).map_err(|e| e.to_string())?;
+    let mut watched_roots = Vec::new();
+
+    let mut watch_new_roots = |watcher: &mut notify::RecommendedWatcher| {
+        for (root, mode) in day_paths.roots() {
+            if root.is_dir() && !watched_roots.contains(&root) {
+                match watcher.watch(&root, mode) {
+                    Ok(()) => watched_roots.push(root),
+                    Err(e) => eprintln!("failed to watch {}: {e}", root.display()),
+                }
+            }
+        }
+    };
+
+    watch_new_roots(&mut watcher);
+
+    let mut previous_answers = Answers::new();
+
+    println!("== day {day} ==");
+    run_day(day, debug, &mut previous_answers);
+
+    for event in &receiver {
+        let event: Event = match event {
+            Ok(event) => event,
+            Err(e) => {
+                eprintln!("watch error: {e}");
+                continue;
+            }
+        };
+
+        if matches!(event.kind, EventKind::Access(_)) {
+            continue;
+        }
+
+        watch_new_roots(&mut watcher);
+
+        if !event.paths.iter().any(|path| day_paths.is_relevant(path)) {
+            continue;
+        }
+
+        while receiver.recv_timeout(DEBOUNCE).is_ok() {}
+
+        println!("\n== day {day}: change detected, re-running ==");
+        run_day(day, debug, &mut previous_answers);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::report::SolutionOutput;
+
+    #[test]
+    fn test_cargo_args() {
+        assert_eq!(
+            cargo_args("4", false, false),
+            [
+                "run",
+                "--quiet",
+                "--release",
+                "--",
+                "4",
+                "--json",
+                "--variants"
+            ]
+        );
+        assert_eq!(
+            cargo_args("4", true, true),
+            [
+                "run",
+                "--quiet",
+                "--",
+                "4",
+                "--json",
+                "--variants",
+                "--examples"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_relevant() {
+        let day_paths = DayPaths::new(Path::new("/aoc/2024"), &Config::default(), 4);
+
+        assert!(day_paths.is_relevant(Path::new("/aoc/2024/src/day4.rs")));
+        assert!(day_paths.is_relevant(Path::new("/aoc/2024/input/2024/day4.txt")));
+        assert!(day_paths.is_relevant(Path::new("/aoc/2024/fixtures/day4/example.toml")));
+        assert!(!day_paths.is_relevant(Path::new("/aoc/2024/src/day5.rs")));
+        assert!(!day_paths.is_relevant(Path::new("/aoc/2024/input/2024/day14.txt")));
+        assert!(!day_paths.is_relevant(Path::new("/aoc/2024/fixtures/day44/example.toml")));
+        assert!(!day_paths.is_relevant(Path::new("/aoc/2024/target/debug/day4.rs")));
+
+        let config = Config::parse(
+            "input_dir = \"../inputs\"\n[days.4]\ninput = \"/tmp/day4-large.txt\"",
+            Path::new(""),
+        )
+        .unwrap();
+        let day_paths = DayPaths::new(Path::new("/aoc/2024"), &config, 4);
+
+        assert!(day_paths.is_relevant(Path::new("/aoc/2024/../inputs/2024/day4.txt")));
+        assert!(day_paths.is_relevant(Path::new("/tmp/day4-large.txt")));
+        assert!(!day_paths.is_relevant(Path::new("/aoc/2024/input/2024/day4.txt")));
+    }
+
+    fn report(expected: Option<&str>, answer: Result<&str, &str>) -> SolutionReport {
+        SolutionReport {
+            day: 4,
+            part: 2,
+            name: None,
+            fixture: expected.map(|_| String::from("example")),
+            profile: None,
+            expected: expected.map(String::from),
+            output: answer
+                .map(|answer| {
+                    SolutionOutput::new(answer.to_string(), Duration::ZERO, Duration::ZERO)
+                })
+                .map_err(String::from),
+        }
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(describe(&report(None, Ok("1")), None), "Day 4 - Part 2: 1");
+        assert_eq!(
+            describe(&report(None, Ok("1")), Some(&Some(String::from("2")))),
+            "Day 4 - Part 2: 1 (was 2)"
+        );
+        assert_eq!(
+            describe(&report(None, Ok("1")), Some(&None)),
+            "Day 4 - Part 2: 1 (was an error)"
+        );
+        assert_eq!(
+            describe(&report(Some("9"), Ok("9")), Some(&Some(String::from("9")))),
+            "Day 4 - Part 2 [example]: 9 (ok; unchanged)"
+        );
+        assert_eq!(
+            describe(&report(Some("9"), Ok("8")), None),
+            "Day 4 - Part 2 [example]: 8 (WRONG, expected 9)"
+        );
+        assert_eq!(
+            describe(&report(Some("9"), Err("FAILED")), None),
+            "Day 4 - Part 2 [example]: FAILED (WRONG, expected 9)"
+        );
+    }
+}